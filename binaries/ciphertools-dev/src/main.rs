@@ -1,12 +1,13 @@
 use crate::ngrams::RankedNgrams;
 use ahash::AHashMap;
+use cipher::alphabet::Alphabet;
 use cipher::cipher::substitution::quipqiup::QuipqiupContext;
 use cipher::language::Language;
 use cipher::ngrams;
-use clap::Parser;
+use clap::{Parser, ValueEnum};
 use csv::Reader;
 use std::fs::File;
-use std::io::{BufRead, BufReader};
+use std::io::{BufRead, BufReader, Write};
 
 #[derive(Parser)]
 struct CliOpts {
@@ -19,6 +20,7 @@ enum Commands {
   BuildLanguage(BuildLanguage),
   TextConfidence(TextConfidence),
   BuildQuipqiupContext(BuildQuipqiupContext),
+  CountNgrams(CountNgrams),
 }
 
 #[derive(Parser)]
@@ -28,6 +30,13 @@ struct BuildLanguage {
   pub bigrams_filepath: String,
   pub trigrams_filepath: String,
   pub index_of_coincidence: f64,
+  #[arg(long)]
+  pub quadgrams_filepath: Option<String>,
+  #[arg(long)]
+  pub pentagrams_filepath: Option<String>,
+  /// Write the compact binary encoding instead of JSON
+  #[arg(long, default_value_t = false)]
+  pub binary: bool,
 }
 
 #[derive(Parser)]
@@ -41,6 +50,48 @@ struct BuildQuipqiupContext {
   pub words_file: String,
 }
 
+#[derive(Parser)]
+struct CountNgrams {
+  pub corpus_file: String,
+  pub n: usize,
+  /// Letters to count; any other character separates words. Greek
+  /// accents are stripped and final sigma is counted as σ
+  #[arg(long, value_enum, default_value_t = CorpusAlphabet::Latin)]
+  pub alphabet: CorpusAlphabet,
+  /// Leave out n-grams seen fewer times, to keep the CSV small
+  #[arg(long, default_value_t = 1)]
+  pub min_count: u64,
+  /// Count runs of up to n words instead of letters, for the word n-grams
+  /// of build-language
+  #[arg(long, default_value_t = false)]
+  pub words: bool,
+}
+
+#[derive(ValueEnum, Clone)]
+enum CorpusAlphabet {
+  Latin,
+  Russian,
+  Greek,
+}
+
+impl CorpusAlphabet {
+  /// Folds a lowercase letter onto the one the alphabet has for it: Greek
+  /// letters lose their accents and diaeresis, and final sigma becomes σ.
+  fn fold(&self, c: char) -> char {
+    match (self, c) {
+      (CorpusAlphabet::Greek, 'ά') => 'α',
+      (CorpusAlphabet::Greek, 'έ') => 'ε',
+      (CorpusAlphabet::Greek, 'ή') => 'η',
+      (CorpusAlphabet::Greek, 'ί' | 'ϊ' | 'ΐ') => 'ι',
+      (CorpusAlphabet::Greek, 'ό') => 'ο',
+      (CorpusAlphabet::Greek, 'ύ' | 'ϋ' | 'ΰ') => 'υ',
+      (CorpusAlphabet::Greek, 'ώ') => 'ω',
+      (CorpusAlphabet::Greek, 'ς') => 'σ',
+      _ => c,
+    }
+  }
+}
+
 fn main() {
   let cli_opts = CliOpts::parse();
 
@@ -52,6 +103,7 @@ fn run(cli_opts: CliOpts) {
     Commands::BuildLanguage(opts) => run_build_language(opts),
     Commands::TextConfidence(opts) => run_text_confidence(opts),
     Commands::BuildQuipqiupContext(opts) => run_build_quipqiup_context(opts),
+    Commands::CountNgrams(opts) => run_count_ngrams(opts),
   }
 }
 
@@ -61,10 +113,23 @@ fn run_build_language(opts: BuildLanguage) {
   let bigrams = parse_ngrams_csv(&opts.bigrams_filepath);
   let trigrams = parse_ngrams_csv(&opts.trigrams_filepath);
 
-  let ranked_ngrams =
+  let mut ranked_ngrams =
     AHashMap::from([(1, unigrams), (2, bigrams), (3, trigrams)]);
+  if let Some(quadgrams_filepath) = opts.quadgrams_filepath {
+    ranked_ngrams.insert(4, parse_ngrams_csv(&quadgrams_filepath));
+  }
+  if let Some(pentagrams_filepath) = opts.pentagrams_filepath {
+    ranked_ngrams.insert(5, parse_ngrams_csv(&pentagrams_filepath));
+  }
   let language = Language::new(words, ranked_ngrams, opts.index_of_coincidence);
 
+  if opts.binary {
+    std::io::stdout()
+      .write_all(&language.to_binary())
+      .expect("Failed to write language");
+    return;
+  }
+
   let language_json = serde_json::to_string_pretty(&language).unwrap();
   println!("{}", language_json);
 }
@@ -90,10 +155,10 @@ fn parse_ngrams_csv(file_path: &str) -> RankedNgrams {
 }
 
 fn run_text_confidence(opts: TextConfidence) {
-  let language_json = std::fs::read_to_string(opts.language_file)
-    .expect("failed to read language");
-  let language: Language =
-    serde_json::from_str(&language_json).expect("failed to parse language");
+  let language_bytes =
+    std::fs::read(opts.language_file).expect("failed to read language");
+  let language =
+    Language::from_bytes(&language_bytes).expect("failed to parse language");
 
   println!(
     "confidence: {}",
@@ -113,3 +178,56 @@ fn run_build_quipqiup_context(opts: BuildQuipqiupContext) {
   let quipqiup_json = serde_json::to_string_pretty(&quipqiup_context).unwrap();
   println!("{}", quipqiup_json);
 }
+
+fn run_count_ngrams(opts: CountNgrams) {
+  let corpus = std::fs::read_to_string(&opts.corpus_file)
+    .unwrap_or_else(|e| panic!("Failed to read {}: {e}", opts.corpus_file));
+
+  let alphabet = match opts.alphabet {
+    CorpusAlphabet::Latin => Alphabet::latin(),
+    CorpusAlphabet::Russian => Alphabet::russian(),
+    CorpusAlphabet::Greek => Alphabet::greek(),
+  };
+
+  let corpus: String = corpus
+    .to_lowercase()
+    .chars()
+    .map(|c| opts.alphabet.fold(c))
+    .collect();
+  let words: Vec<&str> = corpus
+    .split(|c: char| !alphabet.contains(c))
+    .filter(|word| !word.is_empty())
+    .collect();
+
+  let mut counts: AHashMap<String, u64> = AHashMap::new();
+  if opts.words {
+    for len in 1..=opts.n {
+      for window in words.windows(len) {
+        *counts.entry(window.join(" ")).or_insert(0) += 1;
+      }
+    }
+  } else {
+    for word in words {
+      let chars: Vec<char> = word.chars().collect();
+      for window in chars.windows(opts.n) {
+        *counts.entry(window.iter().collect()).or_insert(0) += 1;
+      }
+    }
+  }
+
+  let mut counts: Vec<_> = counts
+    .into_iter()
+    .filter(|(_, count)| *count >= opts.min_count)
+    .collect();
+  counts.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+
+  let mut wtr = csv::Writer::from_writer(std::io::stdout());
+  wtr
+    .write_record([format!("{}gram", opts.n), "freq".to_string()])
+    .expect("Failed to write csv");
+  for (ngram, count) in counts {
+    wtr
+      .write_record([ngram, count.to_string()])
+      .expect("Failed to write csv");
+  }
+}
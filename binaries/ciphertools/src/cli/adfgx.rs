@@ -0,0 +1,65 @@
+use clap::Parser;
+
+use crate::cli::{Alphabet, AnnealDefaults, AnnealOpts};
+
+#[derive(Parser, Debug)]
+pub struct AdfgxOpts {
+  #[arg(long, value_enum, default_value_t = Alphabet::Latin)]
+  pub alphabet: Alphabet,
+  /// Use a 6x6 square of the alphabet and the digits, labelled ADFGVX
+  #[arg(long)]
+  pub adfgvx: bool,
+  /// Two letters, the first merged into the second so the alphabet fits a
+  /// square, or an empty string for no merging. Ignored with --adfgvx
+  #[arg(long, default_value = "ji")]
+  pub merge: String,
+  #[command(subcommand)]
+  pub commands: AdfgxCommands,
+}
+
+#[derive(Parser, Debug)]
+pub enum AdfgxCommands {
+  /// The key is the square keyword and the transposition keyword,
+  /// separated by a comma
+  Encipher(AdfgxEncipherOpts),
+  Decipher(AdfgxDecipherOpts),
+  Solve(AdfgxSolveOpts),
+}
+
+#[derive(Parser, Debug)]
+pub struct AdfgxEncipherOpts {
+  pub key: String,
+  pub plaintext: String,
+}
+
+#[derive(Parser, Debug)]
+pub struct AdfgxDecipherOpts {
+  pub key: String,
+  pub ciphertext: String,
+}
+
+/// Once the transposition is found the square is a substitution of label
+/// pairs, which anneals better than it hill climbs on log-trigram scores.
+#[derive(Debug)]
+pub struct AdfgxAnneal;
+
+impl AnnealDefaults for AdfgxAnneal {
+  const TEMPERATURE: &'static str = "0.1";
+}
+
+#[derive(Parser, Debug)]
+pub struct AdfgxSolveOpts {
+  #[arg(long, default_value_t = 2)]
+  pub min_width: usize,
+  #[arg(long, default_value_t = 10)]
+  pub max_width: usize,
+  /// Hill climbing restarts for the transposition of every width
+  #[arg(long, default_value_t = 20)]
+  pub transposition_restarts: usize,
+  /// Hill climbing iterations for every restart of the transposition
+  #[arg(long, default_value_t = 1000)]
+  pub transposition_iterations: usize,
+  #[command(flatten)]
+  pub anneal: AnnealOpts<AdfgxAnneal>,
+  pub ciphertext: String,
+}
@@ -0,0 +1,32 @@
+use clap::Parser;
+
+use crate::cli::{Alphabet, BruteForceOpts};
+
+#[derive(Parser, Debug)]
+pub struct AffineOpts {
+  #[arg(long, value_enum, default_value_t = Alphabet::Latin)]
+  pub alphabet: Alphabet,
+  #[command(subcommand)]
+  pub commands: AffineCommands,
+}
+
+#[derive(Parser, Debug)]
+pub enum AffineCommands {
+  Encipher(AffineEncipherOpts),
+  Decipher(AffineDecipherOpts),
+  BruteForce(BruteForceOpts),
+}
+
+#[derive(Parser, Debug)]
+pub struct AffineEncipherOpts {
+  /// Written `a,b`, where `a` is coprime with the alphabet length
+  pub key: String,
+  pub plaintext: String,
+}
+
+#[derive(Parser, Debug)]
+pub struct AffineDecipherOpts {
+  /// Written `a,b`, where `a` is coprime with the alphabet length
+  pub key: String,
+  pub ciphertext: String,
+}
@@ -1,6 +1,6 @@
 use clap::Parser;
 
-use crate::cli::{Alphabet, DictionaryOpts};
+use crate::cli::{Alphabet, AnnealOpts, DictionaryOpts};
 
 #[derive(Parser, Debug)]
 pub struct AutokeyOpts {
@@ -17,6 +17,7 @@ pub enum AutokeyCommands {
   Encipher(AutokeyEncipherOpts),
   Decipher(AutokeyDecipherOpts),
   Dictionary(DictionaryOpts),
+  Solve(AutokeySolveOpts),
 }
 
 #[derive(Parser, Debug)]
@@ -30,3 +31,14 @@ pub struct AutokeyDecipherOpts {
   pub key: String,
   pub ciphertext: String,
 }
+
+#[derive(Parser, Debug)]
+pub struct AutokeySolveOpts {
+  #[arg(long, default_value_t = 10)]
+  pub max_primer_length: usize,
+  #[arg(long, default_value_t = false)]
+  pub refine: bool,
+  #[command(flatten)]
+  pub anneal: AnnealOpts,
+  pub ciphertext: String,
+}
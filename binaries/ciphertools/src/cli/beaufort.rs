@@ -0,0 +1,35 @@
+use clap::Parser;
+
+use crate::cli::{Alphabet, DictionaryOpts};
+
+#[derive(Parser, Debug)]
+pub struct BeaufortOpts {
+  #[arg(long, value_enum, default_value_t = Alphabet::Latin)]
+  pub alphabet: Alphabet,
+  #[arg(long, default_value_t = true)]
+  pub skip_whitespace: bool,
+  /// Use Variant Beaufort, which enciphers as plaintext minus key
+  #[arg(long, default_value_t = false)]
+  pub variant: bool,
+  #[command(subcommand)]
+  pub commands: BeaufortCommands,
+}
+
+#[derive(Parser, Debug)]
+pub enum BeaufortCommands {
+  Encipher(BeaufortEncipherOpts),
+  Decipher(BeaufortDecipherOpts),
+  Dictionary(DictionaryOpts),
+}
+
+#[derive(Parser, Debug)]
+pub struct BeaufortEncipherOpts {
+  pub key: String,
+  pub plaintext: String,
+}
+
+#[derive(Parser, Debug)]
+pub struct BeaufortDecipherOpts {
+  pub key: String,
+  pub ciphertext: String,
+}
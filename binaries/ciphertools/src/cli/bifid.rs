@@ -0,0 +1,38 @@
+use clap::Parser;
+
+use crate::cli::{Alphabet, DictionaryOpts, PeriodOpts};
+
+#[derive(Parser, Debug)]
+pub struct BifidOpts {
+  #[arg(long, value_enum, default_value_t = Alphabet::Latin)]
+  pub alphabet: Alphabet,
+  /// Two letters, the first merged into the second so the alphabet fits a
+  /// square, or an empty string for no merging
+  #[arg(long, default_value = "ji")]
+  pub merge: String,
+  /// Letters per block, or 0 to use the whole text as one block
+  #[arg(long, default_value_t = 0)]
+  pub period: usize,
+  #[command(subcommand)]
+  pub commands: BifidCommands,
+}
+
+#[derive(Parser, Debug)]
+pub enum BifidCommands {
+  Encipher(BifidEncipherOpts),
+  Decipher(BifidDecipherOpts),
+  Dictionary(DictionaryOpts),
+  Period(PeriodOpts),
+}
+
+#[derive(Parser, Debug)]
+pub struct BifidEncipherOpts {
+  pub key: String,
+  pub plaintext: String,
+}
+
+#[derive(Parser, Debug)]
+pub struct BifidDecipherOpts {
+  pub key: String,
+  pub ciphertext: String,
+}
@@ -0,0 +1,50 @@
+use clap::Parser;
+
+use crate::cli::{Alphabet, AnnealOpts, DictionaryOpts};
+
+#[derive(Parser, Debug)]
+pub struct ColumnarOpts {
+  #[arg(long, value_enum, default_value_t = Alphabet::Latin)]
+  pub alphabet: Alphabet,
+  #[arg(long, default_value_t = true)]
+  pub skip_whitespace: bool,
+  /// Fill the last row with this letter instead of leaving it incomplete
+  #[arg(long)]
+  pub padding: Option<char>,
+  #[command(subcommand)]
+  pub commands: ColumnarCommands,
+}
+
+#[derive(Parser, Debug)]
+pub enum ColumnarCommands {
+  Encipher(ColumnarEncipherOpts),
+  Decipher(ColumnarDecipherOpts),
+  Dictionary(DictionaryOpts),
+  Solve(ColumnarSolveOpts),
+}
+
+#[derive(Parser, Debug)]
+pub struct ColumnarEncipherOpts {
+  pub key: String,
+  pub plaintext: String,
+}
+
+#[derive(Parser, Debug)]
+pub struct ColumnarDecipherOpts {
+  pub key: String,
+  pub ciphertext: String,
+}
+
+#[derive(Parser, Debug)]
+pub struct ColumnarSolveOpts {
+  #[arg(long, default_value_t = 2)]
+  pub min_width: usize,
+  #[arg(long, default_value_t = 12)]
+  pub max_width: usize,
+  /// Widths up to this one try every column order instead of hill climbing
+  #[arg(long, default_value_t = 7)]
+  pub exhaustive_width: usize,
+  #[command(flatten)]
+  pub anneal: AnnealOpts,
+  pub ciphertext: String,
+}
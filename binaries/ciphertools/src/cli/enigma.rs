@@ -0,0 +1,53 @@
+use clap::Parser;
+
+use crate::cli::AnnealOpts;
+
+#[derive(Parser, Debug)]
+pub struct EnigmaOpts {
+  #[command(subcommand)]
+  pub commands: EnigmaCommands,
+}
+
+#[derive(Parser, Debug)]
+pub enum EnigmaCommands {
+  Encipher(EnigmaEncipherOpts),
+  Decipher(EnigmaDecipherOpts),
+  /// Searches rotor orders and start positions by index of coincidence,
+  /// then ring settings and the plugboard
+  Solve(EnigmaSolveOpts),
+}
+
+#[derive(Parser, Debug)]
+pub struct EnigmaEncipherOpts {
+  /// Written `reflector:rotors:rings:positions[:plugboard]`, e.g.
+  /// `b:ii,iv,v:bul:bla:av bs cg`, with a thin rotor and reflector for the
+  /// M4, e.g. `bthin:beta,ii,iv,i:aaav:vjna`
+  pub key: String,
+  pub plaintext: String,
+}
+
+#[derive(Parser, Debug)]
+pub struct EnigmaDecipherOpts {
+  /// Written `reflector:rotors:rings:positions[:plugboard]`
+  pub key: String,
+  pub ciphertext: String,
+}
+
+#[derive(Parser, Debug)]
+pub struct EnigmaSolveOpts {
+  /// Rotors to choose the three from, separated by commas
+  #[arg(long, default_value = "i,ii,iii,iv,v")]
+  pub rotors: String,
+  #[arg(long, default_value = "b")]
+  pub reflector: String,
+  /// Start positions kept for every rotor order
+  #[arg(long, default_value_t = 3)]
+  pub candidates: usize,
+  /// Candidates with the best index of coincidence, once plugged, whose
+  /// plugboard is then refined on n-grams
+  #[arg(long, default_value_t = 5)]
+  pub finalists: usize,
+  #[command(flatten)]
+  pub anneal: AnnealOpts,
+  pub ciphertext: String,
+}
@@ -0,0 +1,57 @@
+use clap::Parser;
+
+use crate::cli::{Alphabet, AnnealDefaults, AnnealOpts, DictionaryOpts};
+
+#[derive(Parser, Debug)]
+pub struct FourSquareOpts {
+  #[arg(long, value_enum, default_value_t = Alphabet::Latin)]
+  pub alphabet: Alphabet,
+  /// Two letters, the first merged into the second so the alphabet fits a
+  /// square, or an empty string for no merging
+  #[arg(long, default_value = "ji")]
+  pub merge: String,
+  #[arg(long, default_value_t = 'x')]
+  pub filler: char,
+  #[command(subcommand)]
+  pub commands: FourSquareCommands,
+}
+
+#[derive(Parser, Debug)]
+pub enum FourSquareCommands {
+  Encipher(FourSquareEncipherOpts),
+  Decipher(FourSquareDecipherOpts),
+  Dictionary(DictionaryOpts),
+  Solve(FourSquareSolveOpts),
+}
+
+#[derive(Parser, Debug)]
+pub struct FourSquareEncipherOpts {
+  /// Two keywords separated by a comma
+  pub key: String,
+  pub plaintext: String,
+}
+
+#[derive(Parser, Debug)]
+pub struct FourSquareDecipherOpts {
+  /// Two keywords separated by a comma
+  pub key: String,
+  pub ciphertext: String,
+}
+
+/// Two squares to find instead of one, so the solver anneals for longer
+/// than Playfair's.
+#[derive(Debug)]
+pub struct FourSquareAnneal;
+
+impl AnnealDefaults for FourSquareAnneal {
+  const RESTARTS: &'static str = "8";
+  const ITERATIONS: &'static str = "1000000";
+  const TEMPERATURE: &'static str = "0.05";
+}
+
+#[derive(Parser, Debug)]
+pub struct FourSquareSolveOpts {
+  #[command(flatten)]
+  pub anneal: AnnealOpts<FourSquareAnneal>,
+  pub ciphertext: String,
+}
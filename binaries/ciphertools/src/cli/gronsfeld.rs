@@ -0,0 +1,32 @@
+use clap::Parser;
+
+use crate::cli::{Alphabet, DictionaryOpts};
+
+#[derive(Parser, Debug)]
+pub struct GronsfeldOpts {
+  #[arg(long, value_enum, default_value_t = Alphabet::Latin)]
+  pub alphabet: Alphabet,
+  #[arg(long, default_value_t = true)]
+  pub skip_whitespace: bool,
+  #[command(subcommand)]
+  pub commands: GronsfeldCommands,
+}
+
+#[derive(Parser, Debug)]
+pub enum GronsfeldCommands {
+  Encipher(GronsfeldEncipherOpts),
+  Decipher(GronsfeldDecipherOpts),
+  Dictionary(DictionaryOpts),
+}
+
+#[derive(Parser, Debug)]
+pub struct GronsfeldEncipherOpts {
+  pub key: String,
+  pub plaintext: String,
+}
+
+#[derive(Parser, Debug)]
+pub struct GronsfeldDecipherOpts {
+  pub key: String,
+  pub ciphertext: String,
+}
@@ -0,0 +1,47 @@
+use clap::Parser;
+
+use crate::cli::{Alphabet, BruteForceOpts};
+
+#[derive(Parser, Debug)]
+pub struct HillOpts {
+  #[arg(long, value_enum, default_value_t = Alphabet::Latin)]
+  pub alphabet: Alphabet,
+  /// Letter filling up the last block
+  #[arg(long, default_value_t = 'x')]
+  pub padding: char,
+  #[command(subcommand)]
+  pub commands: HillCommands,
+}
+
+#[derive(Parser, Debug)]
+pub enum HillCommands {
+  Encipher(HillEncipherOpts),
+  Decipher(HillDecipherOpts),
+  /// Tries every invertible 2x2 key
+  BruteForce(BruteForceOpts),
+  KnownPlaintext(HillKnownPlaintextOpts),
+}
+
+#[derive(Parser, Debug)]
+pub struct HillEncipherOpts {
+  /// The matrix row by row, as comma-separated numbers or as a keyword
+  pub key: String,
+  pub plaintext: String,
+}
+
+#[derive(Parser, Debug)]
+pub struct HillDecipherOpts {
+  /// The matrix row by row, as comma-separated numbers or as a keyword
+  pub key: String,
+  pub ciphertext: String,
+}
+
+#[derive(Parser, Debug)]
+pub struct HillKnownPlaintextOpts {
+  /// Rows of the key matrix
+  #[arg(long, default_value_t = 2)]
+  pub size: usize,
+  /// Known plaintext, starting where the ciphertext starts
+  pub plaintext: String,
+  pub ciphertext: String,
+}
@@ -0,0 +1,63 @@
+use clap::{Parser, ValueEnum};
+
+use crate::cli::{Alphabet, AnnealDefaults, AnnealOpts};
+
+#[derive(Parser, Debug)]
+pub struct HomophonicOpts {
+  #[arg(long, value_enum, default_value_t = Alphabet::Latin)]
+  pub alphabet: Alphabet,
+  /// Length of every cipher symbol, for symbols written without spaces
+  /// between them, like two-digit codes. Symbols are separated by spaces
+  /// when not given
+  #[arg(long)]
+  pub width: Option<usize>,
+  #[command(subcommand)]
+  pub commands: HomophonicCommands,
+}
+
+#[derive(Parser, Debug)]
+pub enum HomophonicCommands {
+  Encipher(HomophonicEncipherOpts),
+  Decipher(HomophonicDecipherOpts),
+  Solve(HomophonicSolveOpts),
+}
+
+/// Keys give the symbols of every letter, e.g. 'a=01 27,b=02,...'
+#[derive(Parser, Debug)]
+pub struct HomophonicEncipherOpts {
+  pub key: String,
+  pub plaintext: String,
+}
+
+/// Keys give the symbols of every letter, e.g. 'a=01 27,b=02,...'
+#[derive(Parser, Debug)]
+pub struct HomophonicDecipherOpts {
+  pub key: String,
+  pub ciphertext: String,
+}
+
+/// Every symbol is a variable of the key, so the solver anneals for longer
+/// than the simple substitution one, at a temperature suited to log-trigram
+/// scores.
+#[derive(Debug)]
+pub struct HomophonicAnneal;
+
+impl AnnealDefaults for HomophonicAnneal {
+  const ITERATIONS: &'static str = "50000";
+  const TEMPERATURE: &'static str = "0.02";
+}
+
+#[derive(Parser, Debug)]
+pub struct HomophonicSolveOpts {
+  #[arg(long, value_enum, default_value_t = Seed::Frequency)]
+  pub seed: Seed,
+  #[command(flatten)]
+  pub anneal: AnnealOpts<HomophonicAnneal>,
+  pub ciphertext: String,
+}
+
+#[derive(ValueEnum, Debug, Clone)]
+pub enum Seed {
+  Random,
+  Frequency,
+}
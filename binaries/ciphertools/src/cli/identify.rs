@@ -0,0 +1,10 @@
+use clap::Parser;
+
+use crate::cli::Alphabet;
+
+#[derive(Parser, Debug)]
+pub struct IdentifyOpts {
+  #[arg(long, value_enum, default_value_t = Alphabet::Latin)]
+  pub alphabet: Alphabet,
+  pub ciphertext: String,
+}
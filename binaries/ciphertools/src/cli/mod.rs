@@ -1,23 +1,71 @@
+pub mod adfgx;
+pub mod affine;
 pub mod autokey;
+pub mod beaufort;
+pub mod bifid;
 pub mod caesar;
+pub mod columnar;
+pub mod enigma;
+pub mod four_square;
+pub mod gronsfeld;
+pub mod hill;
+pub mod homophonic;
+pub mod identify;
+pub mod playfair;
+pub mod porta;
+pub mod quagmire;
+pub mod rail_fence;
+pub mod route;
+pub mod running_key;
 pub mod substitution;
+pub mod trifid;
+pub mod two_square;
 pub mod vigenere;
 
+use crate::cli::adfgx::{AdfgxCommands, AdfgxOpts};
+use crate::cli::affine::AffineOpts;
 use crate::cli::autokey::AutokeyOpts;
+use crate::cli::beaufort::BeaufortOpts;
+use crate::cli::bifid::BifidOpts;
+use crate::cli::columnar::ColumnarOpts;
+use crate::cli::enigma::EnigmaOpts;
+use crate::cli::four_square::{FourSquareCommands, FourSquareOpts};
+use crate::cli::gronsfeld::GronsfeldOpts;
+use crate::cli::hill::HillOpts;
+use crate::cli::homophonic::{HomophonicCommands, HomophonicOpts};
+use crate::cli::identify::IdentifyOpts;
+use crate::cli::playfair::{PlayfairCommands, PlayfairOpts};
+use crate::cli::porta::PortaOpts;
+use crate::cli::quagmire::QuagmireOpts;
+use crate::cli::rail_fence::RailFenceOpts;
+use crate::cli::route::RouteOpts;
+use crate::cli::running_key::RunningKeyOpts;
+use crate::cli::trifid::TrifidOpts;
+use crate::cli::two_square::{TwoSquareCommands, TwoSquareOpts};
 use crate::cli::vigenere::VigenereOpts;
 use crate::cli::{caesar::CaesarOpts, substitution::SubstitutionOpts};
-use cipher::alphabet;
 use cipher::language::{self, Language};
+use cipher::{alphabet, search};
 use clap::{Parser, ValueEnum};
-use num_cpus;
+use std::marker::PhantomData;
 use std::sync::Arc;
 
 #[derive(Parser, Debug)]
 pub struct CliOpts {
   #[arg(short = 'j', default_value_t = num_cpus::get())]
   pub jobs: usize,
-  #[clap(long, hide = true, value_enum, default_value_t = Confidence::Chi2Trigrams)]
-  pub confidence_algorithm: Confidence,
+  /// How candidate plaintexts are scored. Defaults to log-trigrams for the
+  /// annealing solvers that need a smooth score, and to chi2-trigrams
+  /// otherwise
+  #[arg(long, value_enum)]
+  pub confidence_algorithm: Option<Confidence>,
+  /// Defaults to the language written in the subcommand's alphabet
+  #[arg(long, value_enum, conflicts_with = "language_file")]
+  pub language: Option<BundledLanguage>,
+  /// Language model built with `ciphertools-dev build-language`, as JSON or
+  /// in its binary encoding
+  #[arg(long)]
+  pub language_file: Option<String>,
   #[command(subcommand)]
   pub commands: Commands,
 }
@@ -26,8 +74,27 @@ pub struct CliOpts {
 pub enum Commands {
   Autokey(AutokeyOpts),
   Vigenere(VigenereOpts),
+  Beaufort(BeaufortOpts),
+  Gronsfeld(GronsfeldOpts),
+  Porta(PortaOpts),
+  Quagmire(QuagmireOpts),
+  RunningKey(RunningKeyOpts),
   Substitution(SubstitutionOpts),
+  Homophonic(HomophonicOpts),
   Caesar(CaesarOpts),
+  Affine(AffineOpts),
+  Hill(HillOpts),
+  Columnar(ColumnarOpts),
+  RailFence(RailFenceOpts),
+  Route(RouteOpts),
+  Playfair(PlayfairOpts),
+  TwoSquare(TwoSquareOpts),
+  FourSquare(FourSquareOpts),
+  Bifid(BifidOpts),
+  Trifid(TrifidOpts),
+  Adfgx(AdfgxOpts),
+  Enigma(EnigmaOpts),
+  Identify(IdentifyOpts),
 }
 
 #[derive(Parser, Debug)]
@@ -41,12 +108,57 @@ pub struct BruteForceOpts {
   pub ciphertext: String,
 }
 
+#[derive(Parser, Debug)]
+pub struct PeriodOpts {
+  #[arg(long, default_value_t = 20)]
+  pub max_period: usize,
+  pub ciphertext: String,
+}
+
+/// Default annealing options of a solver, as clap default values. Plain
+/// hill climbing suits most ciphers; solvers with many local optima
+/// override these.
+pub trait AnnealDefaults {
+  const RESTARTS: &'static str = "20";
+  const ITERATIONS: &'static str = "5000";
+  const TEMPERATURE: &'static str = "0";
+}
+
+#[derive(Debug)]
+pub struct HillClimb;
+
+impl AnnealDefaults for HillClimb {}
+
+#[derive(Parser, Debug)]
+pub struct AnnealOpts<D: AnnealDefaults = HillClimb> {
+  #[arg(long, default_value = D::RESTARTS)]
+  pub restarts: usize,
+  #[arg(long, default_value = D::ITERATIONS)]
+  pub iterations: usize,
+  #[arg(long, default_value = D::TEMPERATURE)]
+  pub temperature: f64,
+  #[arg(skip)]
+  pub defaults: PhantomData<D>,
+}
+
 #[allow(clippy::enum_variant_names)]
 #[derive(ValueEnum, Debug, Clone)]
 pub enum Confidence {
   Chi2Unigrams,
   Chi2Bigrams,
   Chi2Trigrams,
+  LogBigrams,
+  LogTrigrams,
+  LogQuadgrams,
+  LogPentagrams,
+  Words,
+}
+
+#[derive(ValueEnum, Debug, Clone)]
+pub enum BundledLanguage {
+  English,
+  Russian,
+  Greek,
 }
 
 #[derive(ValueEnum, Debug, Clone)]
@@ -60,8 +172,8 @@ impl Confidence {
   pub fn into_get_confidence(
     self,
     language: Language,
-  ) -> language::GetConfidence {
-    match self {
+  ) -> Result<language::GetConfidence, String> {
+    let get_confidence = match self {
       Confidence::Chi2Unigrams => {
         language::GetConfidence::new(Arc::new(move |text: &str| {
           language.text_confidence_chi2_unigram(text)
@@ -77,6 +189,145 @@ impl Confidence {
           language.text_confidence_chi2_bigram(text)
         }))
       }
+      Confidence::LogBigrams => log_ngram_confidence(&language, 2)?,
+      Confidence::LogTrigrams => log_ngram_confidence(&language, 3)?,
+      Confidence::LogQuadgrams => log_ngram_confidence(&language, 4)?,
+      Confidence::LogPentagrams => log_ngram_confidence(&language, 5)?,
+      Confidence::Words => {
+        let word_model = language.word_model();
+        if word_model.is_empty() {
+          return Err("Language has no word statistics".to_string());
+        }
+        language::GetConfidence::new(Arc::new(move |text: &str| {
+          word_model.text_confidence(text)
+        }))
+      }
+    };
+
+    Ok(get_confidence)
+  }
+}
+
+fn log_ngram_confidence(
+  language: &Language,
+  n: usize,
+) -> Result<language::GetConfidence, String> {
+  let table = language
+    .log_ngram_table(n)
+    .ok_or_else(|| format!("Language has no {n}-gram statistics"))?;
+
+  Ok(language::GetConfidence::new(Arc::new(move |text: &str| {
+    table.text_confidence(text)
+  })))
+}
+
+/// Parses `--merge`: two letters, the first merged into the second, or an
+/// empty string for no merging.
+pub fn parse_merge(merge: &str) -> Result<Option<(char, char)>, String> {
+  let letters: Vec<char> = merge.chars().collect();
+  match letters[..] {
+    [] => Ok(None),
+    [from, to] => Ok(Some((from, to))),
+    _ => Err(format!(
+      "Invalid merge '{merge}', expected two letters or an empty string"
+    )),
+  }
+}
+
+impl Commands {
+  pub fn alphabet(&self) -> &Alphabet {
+    match self {
+      Commands::Autokey(opts) => &opts.alphabet,
+      Commands::Vigenere(opts) => &opts.alphabet,
+      Commands::Beaufort(opts) => &opts.alphabet,
+      Commands::Gronsfeld(opts) => &opts.alphabet,
+      Commands::Porta(opts) => &opts.alphabet,
+      Commands::Quagmire(opts) => &opts.alphabet,
+      Commands::RunningKey(opts) => &opts.alphabet,
+      Commands::Substitution(opts) => &opts.alphabet,
+      Commands::Homophonic(opts) => &opts.alphabet,
+      Commands::Caesar(opts) => &opts.alphabet,
+      Commands::Affine(opts) => &opts.alphabet,
+      Commands::Hill(opts) => &opts.alphabet,
+      Commands::Columnar(opts) => &opts.alphabet,
+      Commands::RailFence(opts) => &opts.alphabet,
+      Commands::Route(opts) => &opts.alphabet,
+      Commands::Playfair(opts) => &opts.alphabet,
+      Commands::TwoSquare(opts) => &opts.alphabet,
+      Commands::FourSquare(opts) => &opts.alphabet,
+      Commands::Bifid(opts) => &opts.alphabet,
+      Commands::Trifid(opts) => &opts.alphabet,
+      Commands::Adfgx(opts) => &opts.alphabet,
+      // The machine only has the Latin letters.
+      Commands::Enigma(_) => &Alphabet::Latin,
+      Commands::Identify(opts) => &opts.alphabet,
+    }
+  }
+
+  /// Scoring for commands run without `--confidence-algorithm`.
+  pub fn default_confidence(&self) -> Confidence {
+    match self {
+      Commands::Playfair(PlayfairOpts {
+        commands: PlayfairCommands::Solve(_),
+        ..
+      })
+      | Commands::TwoSquare(TwoSquareOpts {
+        commands: TwoSquareCommands::Solve(_),
+        ..
+      })
+      | Commands::FourSquare(FourSquareOpts {
+        commands: FourSquareCommands::Solve(_),
+        ..
+      })
+      | Commands::Homophonic(HomophonicOpts {
+        commands: HomophonicCommands::Solve(_),
+        ..
+      })
+      | Commands::Adfgx(AdfgxOpts {
+        commands: AdfgxCommands::Solve(_),
+        ..
+      }) => Confidence::LogTrigrams,
+      _ => Confidence::Chi2Trigrams,
+    }
+  }
+}
+
+impl BundledLanguage {
+  pub fn into_language(self) -> Language {
+    match self {
+      BundledLanguage::English => Language::english(),
+      BundledLanguage::Russian => Language::russian(),
+      BundledLanguage::Greek => Language::greek(),
+    }
+  }
+}
+
+impl From<&Alphabet> for BundledLanguage {
+  fn from(val: &Alphabet) -> Self {
+    match val {
+      Alphabet::Russian => BundledLanguage::Russian,
+      Alphabet::Greek => BundledLanguage::Greek,
+      Alphabet::Latin => BundledLanguage::English,
+    }
+  }
+}
+
+impl<D: AnnealDefaults> From<&AnnealOpts<D>> for search::AnnealOptions {
+  fn from(val: &AnnealOpts<D>) -> Self {
+    search::AnnealOptions {
+      iterations: val.iterations,
+      temperature: val.temperature,
+    }
+  }
+}
+
+impl From<quagmire::Variant> for cipher::cipher::quagmire::Variant {
+  fn from(val: quagmire::Variant) -> Self {
+    match val {
+      quagmire::Variant::I => cipher::cipher::quagmire::Variant::I,
+      quagmire::Variant::II => cipher::cipher::quagmire::Variant::II,
+      quagmire::Variant::III => cipher::cipher::quagmire::Variant::III,
+      quagmire::Variant::IV => cipher::cipher::quagmire::Variant::IV,
     }
   }
 }
@@ -0,0 +1,55 @@
+use clap::Parser;
+
+use crate::cli::{Alphabet, AnnealDefaults, AnnealOpts, DictionaryOpts};
+
+#[derive(Parser, Debug)]
+pub struct PlayfairOpts {
+  #[arg(long, value_enum, default_value_t = Alphabet::Latin)]
+  pub alphabet: Alphabet,
+  /// Two letters, the first merged into the second so the alphabet fits a
+  /// square, or an empty string for no merging
+  #[arg(long, default_value = "ji")]
+  pub merge: String,
+  #[arg(long, default_value_t = 'x')]
+  pub filler: char,
+  #[command(subcommand)]
+  pub commands: PlayfairCommands,
+}
+
+#[derive(Parser, Debug)]
+pub enum PlayfairCommands {
+  Encipher(PlayfairEncipherOpts),
+  Decipher(PlayfairDecipherOpts),
+  Dictionary(DictionaryOpts),
+  Solve(PlayfairSolveOpts),
+}
+
+#[derive(Parser, Debug)]
+pub struct PlayfairEncipherOpts {
+  pub key: String,
+  pub plaintext: String,
+}
+
+#[derive(Parser, Debug)]
+pub struct PlayfairDecipherOpts {
+  pub key: String,
+  pub ciphertext: String,
+}
+
+/// Playfair has many local optima, so its solver anneals for long, and
+/// each restart solves a few hundred letters more often than not.
+#[derive(Debug)]
+pub struct PlayfairAnneal;
+
+impl AnnealDefaults for PlayfairAnneal {
+  const RESTARTS: &'static str = "8";
+  const ITERATIONS: &'static str = "400000";
+  const TEMPERATURE: &'static str = "0.1";
+}
+
+#[derive(Parser, Debug)]
+pub struct PlayfairSolveOpts {
+  #[command(flatten)]
+  pub anneal: AnnealOpts<PlayfairAnneal>,
+  pub ciphertext: String,
+}
@@ -0,0 +1,32 @@
+use clap::Parser;
+
+use crate::cli::{Alphabet, DictionaryOpts};
+
+#[derive(Parser, Debug)]
+pub struct PortaOpts {
+  #[arg(long, value_enum, default_value_t = Alphabet::Latin)]
+  pub alphabet: Alphabet,
+  #[arg(long, default_value_t = true)]
+  pub skip_whitespace: bool,
+  #[command(subcommand)]
+  pub commands: PortaCommands,
+}
+
+#[derive(Parser, Debug)]
+pub enum PortaCommands {
+  Encipher(PortaEncipherOpts),
+  Decipher(PortaDecipherOpts),
+  Dictionary(DictionaryOpts),
+}
+
+#[derive(Parser, Debug)]
+pub struct PortaEncipherOpts {
+  pub key: String,
+  pub plaintext: String,
+}
+
+#[derive(Parser, Debug)]
+pub struct PortaDecipherOpts {
+  pub key: String,
+  pub ciphertext: String,
+}
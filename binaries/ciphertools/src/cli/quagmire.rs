@@ -0,0 +1,49 @@
+use clap::{Parser, ValueEnum};
+
+use crate::cli::Alphabet;
+
+#[derive(Parser, Debug)]
+pub struct QuagmireOpts {
+  #[arg(long, value_enum, default_value_t = Alphabet::Latin)]
+  pub alphabet: Alphabet,
+  #[arg(long, value_enum)]
+  pub variant: Variant,
+  /// Mixes the keyword alphabets by reading them off in columns
+  #[arg(long, default_value_t = false)]
+  pub columnar: bool,
+  #[arg(long, default_value_t = true)]
+  pub skip_whitespace: bool,
+  #[command(subcommand)]
+  pub commands: QuagmireCommands,
+}
+
+#[derive(Parser, Debug)]
+pub enum QuagmireCommands {
+  Encipher(QuagmireEncipherOpts),
+  Decipher(QuagmireDecipherOpts),
+}
+
+/// Keys are 'keyword,indicator', or 'plain keyword,cipher
+/// keyword,indicator' for Quagmire IV
+#[derive(Parser, Debug)]
+pub struct QuagmireEncipherOpts {
+  pub key: String,
+  pub plaintext: String,
+}
+
+/// Keys are 'keyword,indicator', or 'plain keyword,cipher
+/// keyword,indicator' for Quagmire IV
+#[derive(Parser, Debug)]
+pub struct QuagmireDecipherOpts {
+  pub key: String,
+  pub ciphertext: String,
+}
+
+#[allow(clippy::upper_case_acronyms)]
+#[derive(ValueEnum, Debug, Clone)]
+pub enum Variant {
+  I,
+  II,
+  III,
+  IV,
+}
@@ -0,0 +1,38 @@
+use clap::Parser;
+
+use crate::cli::{Alphabet, BruteForceOpts};
+
+#[derive(Parser, Debug)]
+pub struct RailFenceOpts {
+  /// Only picks the language used to score brute-force results
+  #[arg(long, value_enum, default_value_t = Alphabet::Latin)]
+  pub alphabet: Alphabet,
+  #[arg(long, default_value_t = true)]
+  pub skip_whitespace: bool,
+  /// Largest number of rails tried by brute-force
+  #[arg(long, default_value_t = 10)]
+  pub max_rails: usize,
+  #[command(subcommand)]
+  pub commands: RailFenceCommands,
+}
+
+#[derive(Parser, Debug)]
+pub enum RailFenceCommands {
+  Encipher(RailFenceEncipherOpts),
+  Decipher(RailFenceDecipherOpts),
+  BruteForce(BruteForceOpts),
+}
+
+#[derive(Parser, Debug)]
+pub struct RailFenceEncipherOpts {
+  /// Written `rails` or `rails,offset`
+  pub key: String,
+  pub plaintext: String,
+}
+
+#[derive(Parser, Debug)]
+pub struct RailFenceDecipherOpts {
+  /// Written `rails` or `rails,offset`
+  pub key: String,
+  pub ciphertext: String,
+}
@@ -0,0 +1,40 @@
+use clap::Parser;
+
+use crate::cli::{Alphabet, BruteForceOpts};
+
+#[derive(Parser, Debug)]
+pub struct RouteOpts {
+  /// Only picks the language used to score brute-force results
+  #[arg(long, value_enum, default_value_t = Alphabet::Latin)]
+  pub alphabet: Alphabet,
+  #[arg(long, default_value_t = true)]
+  pub skip_whitespace: bool,
+  /// Widest grid tried by brute-force
+  #[arg(long, default_value_t = 20)]
+  pub max_width: usize,
+  #[command(subcommand)]
+  pub commands: RouteCommands,
+}
+
+#[derive(Parser, Debug)]
+pub enum RouteCommands {
+  Encipher(RouteEncipherOpts),
+  Decipher(RouteDecipherOpts),
+  BruteForce(BruteForceOpts),
+}
+
+#[derive(Parser, Debug)]
+pub struct RouteEncipherOpts {
+  /// Written `path,width`, where the path is spiral, boustrophedon or
+  /// diagonal
+  pub key: String,
+  pub plaintext: String,
+}
+
+#[derive(Parser, Debug)]
+pub struct RouteDecipherOpts {
+  /// Written `path,width`, where the path is spiral, boustrophedon or
+  /// diagonal
+  pub key: String,
+  pub ciphertext: String,
+}
@@ -0,0 +1,57 @@
+use clap::Parser;
+
+use crate::cli::Alphabet;
+
+#[derive(Parser, Debug)]
+pub struct RunningKeyOpts {
+  #[arg(long, value_enum, default_value_t = Alphabet::Latin)]
+  pub alphabet: Alphabet,
+  #[arg(long, default_value_t = true)]
+  pub skip_whitespace: bool,
+  #[command(subcommand)]
+  pub commands: RunningKeyCommands,
+}
+
+#[derive(Parser, Debug)]
+pub enum RunningKeyCommands {
+  Encipher(RunningKeyEncipherOpts),
+  Decipher(RunningKeyDecipherOpts),
+  /// Tries the key at every offset of a book
+  Search(RunningKeySearchOpts),
+  /// Tries a probable word at every position of the ciphertext
+  Crib(RunningKeyCribOpts),
+}
+
+#[derive(Parser, Debug)]
+pub struct RunningKeyEncipherOpts {
+  /// Letters of the key text skipped before the key starts
+  #[arg(long, default_value_t = 0)]
+  pub offset: usize,
+  /// Text file the key is taken from
+  pub key_file: String,
+  pub plaintext: String,
+}
+
+#[derive(Parser, Debug)]
+pub struct RunningKeyDecipherOpts {
+  /// Letters of the key text skipped before the key starts
+  #[arg(long, default_value_t = 0)]
+  pub offset: usize,
+  /// Text file the key is taken from
+  pub key_file: String,
+  pub ciphertext: String,
+}
+
+#[derive(Parser, Debug)]
+pub struct RunningKeySearchOpts {
+  pub book_file: String,
+  pub ciphertext: String,
+}
+
+#[derive(Parser, Debug)]
+pub struct RunningKeyCribOpts {
+  #[arg(long, default_value_t = 10)]
+  pub candidates: usize,
+  pub crib: String,
+  pub ciphertext: String,
+}
@@ -1,6 +1,6 @@
-use clap::Parser;
+use clap::{Parser, ValueEnum};
 
-use crate::cli::Alphabet;
+use crate::cli::{Alphabet, AnnealOpts};
 
 #[derive(Parser, Debug)]
 pub struct SubstitutionOpts {
@@ -14,16 +14,55 @@ pub struct SubstitutionOpts {
 pub enum SubstitutionCommands {
   Encipher(SubstitutionEncipherOpts),
   Decipher(SubstitutionDecipherOpts),
+  Quipqiup(SubstitutionQuipqiupOpts),
+  Solve(SubstitutionSolveOpts),
 }
 
 #[derive(Parser, Debug)]
+#[command(allow_missing_positional = true)]
 pub struct SubstitutionEncipherOpts {
-  pub key: String,
+  #[command(flatten)]
+  pub key: SubstitutionKeyOpts,
   pub plaintext: String,
 }
 
 #[derive(Parser, Debug)]
+#[command(allow_missing_positional = true)]
 pub struct SubstitutionDecipherOpts {
-  pub key: String,
+  #[command(flatten)]
+  pub key: SubstitutionKeyOpts,
   pub ciphertext: String,
 }
+
+#[derive(Parser, Debug)]
+pub struct SubstitutionKeyOpts {
+  /// Builds the key from a keyword, followed by the rest of the alphabet
+  #[arg(long, conflicts_with = "key")]
+  pub keyword: Option<String>,
+  /// Mixes the keyword alphabet by reading it off in columns
+  #[arg(long, requires = "keyword", conflicts_with = "key")]
+  pub columnar: bool,
+  #[arg(required_unless_present = "keyword")]
+  pub key: Option<String>,
+}
+
+#[derive(Parser, Debug)]
+pub struct SubstitutionQuipqiupOpts {
+  pub quipqiup_file: String,
+  pub ciphertext: String,
+}
+
+#[derive(Parser, Debug)]
+pub struct SubstitutionSolveOpts {
+  #[arg(long, value_enum, default_value_t = Seed::Frequency)]
+  pub seed: Seed,
+  #[command(flatten)]
+  pub anneal: AnnealOpts,
+  pub ciphertext: String,
+}
+
+#[derive(ValueEnum, Debug, Clone)]
+pub enum Seed {
+  Random,
+  Frequency,
+}
@@ -0,0 +1,37 @@
+use clap::Parser;
+
+use crate::cli::{Alphabet, DictionaryOpts, PeriodOpts};
+
+#[derive(Parser, Debug)]
+pub struct TrifidOpts {
+  #[arg(long, value_enum, default_value_t = Alphabet::Latin)]
+  pub alphabet: Alphabet,
+  /// Symbol added to the alphabet to fill the 27 cells of the cube
+  #[arg(long, default_value_t = '+')]
+  pub filler: char,
+  /// Letters per block, or 0 to use the whole text as one block
+  #[arg(long, default_value_t = 5)]
+  pub period: usize,
+  #[command(subcommand)]
+  pub commands: TrifidCommands,
+}
+
+#[derive(Parser, Debug)]
+pub enum TrifidCommands {
+  Encipher(TrifidEncipherOpts),
+  Decipher(TrifidDecipherOpts),
+  Dictionary(DictionaryOpts),
+  Period(PeriodOpts),
+}
+
+#[derive(Parser, Debug)]
+pub struct TrifidEncipherOpts {
+  pub key: String,
+  pub plaintext: String,
+}
+
+#[derive(Parser, Debug)]
+pub struct TrifidDecipherOpts {
+  pub key: String,
+  pub ciphertext: String,
+}
@@ -0,0 +1,60 @@
+use clap::Parser;
+
+use crate::cli::{Alphabet, AnnealDefaults, AnnealOpts, DictionaryOpts};
+
+#[derive(Parser, Debug)]
+pub struct TwoSquareOpts {
+  #[arg(long, value_enum, default_value_t = Alphabet::Latin)]
+  pub alphabet: Alphabet,
+  /// Two letters, the first merged into the second so the alphabet fits a
+  /// square, or an empty string for no merging
+  #[arg(long, default_value = "ji")]
+  pub merge: String,
+  #[arg(long, default_value_t = 'x')]
+  pub filler: char,
+  /// Stack the squares instead of placing them side by side
+  #[arg(long, default_value_t = false)]
+  pub vertical: bool,
+  #[command(subcommand)]
+  pub commands: TwoSquareCommands,
+}
+
+#[derive(Parser, Debug)]
+pub enum TwoSquareCommands {
+  Encipher(TwoSquareEncipherOpts),
+  Decipher(TwoSquareDecipherOpts),
+  Dictionary(DictionaryOpts),
+  Solve(TwoSquareSolveOpts),
+}
+
+#[derive(Parser, Debug)]
+pub struct TwoSquareEncipherOpts {
+  /// Two keywords separated by a comma
+  pub key: String,
+  pub plaintext: String,
+}
+
+#[derive(Parser, Debug)]
+pub struct TwoSquareDecipherOpts {
+  /// Two keywords separated by a comma
+  pub key: String,
+  pub ciphertext: String,
+}
+
+/// Two squares to find instead of one, so the solver anneals for longer
+/// than Playfair's.
+#[derive(Debug)]
+pub struct TwoSquareAnneal;
+
+impl AnnealDefaults for TwoSquareAnneal {
+  const RESTARTS: &'static str = "8";
+  const ITERATIONS: &'static str = "1000000";
+  const TEMPERATURE: &'static str = "0.05";
+}
+
+#[derive(Parser, Debug)]
+pub struct TwoSquareSolveOpts {
+  #[command(flatten)]
+  pub anneal: AnnealOpts<TwoSquareAnneal>,
+  pub ciphertext: String,
+}
@@ -1,6 +1,6 @@
 use clap::Parser;
 
-use crate::cli::{Alphabet, DictionaryOpts};
+use crate::cli::{Alphabet, AnnealOpts, DictionaryOpts};
 
 #[derive(Parser, Debug)]
 pub struct VigenereOpts {
@@ -17,6 +17,8 @@ pub enum VigenereCommands {
   Encipher(VigenereEncipherOpts),
   Decipher(VigenereDecipherOpts),
   Dictionary(DictionaryOpts),
+  KeyLength(VigenereKeyLengthOpts),
+  Solve(VigenereSolveOpts),
 }
 
 #[derive(Parser, Debug)]
@@ -30,3 +32,23 @@ pub struct VigenereDecipherOpts {
   pub key: String,
   pub ciphertext: String,
 }
+
+#[derive(Parser, Debug)]
+pub struct VigenereKeyLengthOpts {
+  #[arg(long, default_value_t = 20)]
+  pub max_key_length: usize,
+  pub ciphertext: String,
+}
+
+#[derive(Parser, Debug)]
+pub struct VigenereSolveOpts {
+  #[arg(long, default_value_t = 20)]
+  pub max_key_length: usize,
+  #[arg(long, default_value_t = 3)]
+  pub key_lengths: usize,
+  #[arg(long, default_value_t = false)]
+  pub refine: bool,
+  #[command(flatten)]
+  pub anneal: AnnealOpts,
+  pub ciphertext: String,
+}
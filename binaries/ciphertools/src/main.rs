@@ -4,14 +4,21 @@ mod threads;
 
 use crate::cli::CliOpts;
 use crate::scoreboard::Scoreboard;
+use cipher::alphabet;
 use cipher::cipher::{
-  Decipher, Encipher, IntoDecipherKey, KeysIterator, autokey, caesar,
-  substitution, vigenere,
+  Decipher, Encipher, IntoDecipherKey, KeysIterator, PartialDecipher, adfgx,
+  affine, autokey, beaufort, bifid, caesar, columnar, enigma, four_square,
+  gronsfeld, hill, homophonic, playfair, porta, quagmire, rail_fence, route,
+  running_key, substitution, substitution::quipqiup::QuipqiupContext, trifid,
+  two_square, vigenere,
 };
+use cipher::identify::Features;
 use cipher::language::{GetConfidence, Language};
+use cipher::periodicity::PeriodCandidate;
+use cipher::search::AnnealOptions;
 use clap::Parser;
 use crossbeam::channel::Sender;
-use rayon::{ThreadPool, ThreadPoolBuilder};
+use rayon::{ThreadPool, ThreadPoolBuilder, prelude::*};
 use std::fmt::Display;
 use std::fs::File;
 use std::sync::Arc;
@@ -21,9 +28,11 @@ use std::{
 };
 use threads::{CandidateCollectorMsg, spawn_candidate_collector};
 
+#[derive(Clone)]
 struct CiphertoolsContext {
+  language: Language,
   get_confidence: GetConfidence,
-  pool: ThreadPool,
+  pool: Arc<ThreadPool>,
 }
 
 fn main() {
@@ -34,21 +43,54 @@ fn main() {
   });
 }
 
-fn run(opts: CliOpts) -> Result<(), String> {
-  let language = Language::english();
-  let pool = ThreadPoolBuilder::new()
-    .num_threads(opts.jobs)
-    .build()
-    .unwrap_or_else(|e| {
-      eprintln!("Failed to build thread pool: {e}");
-      std::process::exit(1);
-    });
+impl CiphertoolsContext {
+  fn new(
+    jobs: usize,
+    confidence_algorithm: cli::Confidence,
+    language: cli::BundledLanguage,
+    language_file: Option<String>,
+  ) -> Result<Self, String> {
+    let language = match language_file {
+      Some(language_file) => read_language_file(&language_file)?,
+      None => language.into_language(),
+    };
+    let pool = ThreadPoolBuilder::new()
+      .num_threads(jobs)
+      .build()
+      .unwrap_or_else(|e| {
+        eprintln!("Failed to build thread pool: {e}");
+        std::process::exit(1);
+      });
 
-  let ciphertools_context = CiphertoolsContext {
-    get_confidence: opts
-      .confidence_algorithm
-      .into_get_confidence(language.clone()),
-    pool,
+    Ok(CiphertoolsContext {
+      get_confidence: confidence_algorithm
+        .into_get_confidence(language.clone())?,
+      language,
+      pool: Arc::new(pool),
+    })
+  }
+}
+
+fn read_language_file(language_file: &str) -> Result<Language, String> {
+  let bytes = std::fs::read(language_file)
+    .map_err(|e| format!("Failed to open file '{}': {}", language_file, e))?;
+
+  Language::from_bytes(&bytes)
+    .map_err(|e| format!("Failed to parse '{}': {}", language_file, e))
+}
+
+fn run(opts: CliOpts) -> Result<(), String> {
+  let language = opts
+    .language
+    .unwrap_or_else(|| opts.commands.alphabet().into());
+  let jobs = opts.jobs;
+  let confidence_algorithm = opts
+    .confidence_algorithm
+    .unwrap_or_else(|| opts.commands.default_confidence());
+  let language_file = opts.language_file;
+  // Only commands that score candidates need a language model.
+  let ciphertools_context = move || {
+    CiphertoolsContext::new(jobs, confidence_algorithm, language, language_file)
   };
 
   match opts.commands {
@@ -74,13 +116,16 @@ fn run(opts: CliOpts) -> Result<(), String> {
         }
         cli::autokey::AutokeyCommands::Dictionary(opts) => {
           let (tx, candidate_collector_handle) =
-            spawn_candidate_collector(ciphertools_context);
+            spawn_candidate_collector(ciphertools_context()?);
           let dictionary_iter =
             get_dictionary_iter(opts.dictionary_file, context.clone())?;
           run_dictionary_attack(context, opts.ciphertext, dictionary_iter, tx);
 
           let _ = candidate_collector_handle.join();
         }
+        cli::autokey::AutokeyCommands::Solve(opts) => {
+          run_autokey_solve(context, opts, ciphertools_context()?);
+        }
       }
     }
     cli::Commands::Vigenere(opts) => {
@@ -105,7 +150,109 @@ fn run(opts: CliOpts) -> Result<(), String> {
         }
         cli::vigenere::VigenereCommands::Dictionary(opts) => {
           let (tx, candidate_collector_handle) =
-            spawn_candidate_collector(ciphertools_context);
+            spawn_candidate_collector(ciphertools_context()?);
+          let dictionary_iter =
+            get_dictionary_iter(opts.dictionary_file, context.clone())?;
+          run_dictionary_attack(context, opts.ciphertext, dictionary_iter, tx);
+
+          let _ = candidate_collector_handle.join();
+        }
+        cli::vigenere::VigenereCommands::KeyLength(opts) => {
+          run_key_length(context, opts, &ciphertools_context()?.language);
+        }
+        cli::vigenere::VigenereCommands::Solve(opts) => {
+          run_vigenere_solve(context, opts, ciphertools_context()?);
+        }
+      }
+    }
+    cli::Commands::Beaufort(opts) => {
+      let context = if opts.variant {
+        beaufort::Beaufort::variant(opts.alphabet.into(), opts.skip_whitespace)
+      } else {
+        beaufort::Beaufort::new(opts.alphabet.into(), opts.skip_whitespace)
+      };
+      match opts.commands {
+        cli::beaufort::BeaufortCommands::Encipher(opts) => {
+          run_encipher(
+            &beaufort::BeaufortKey::try_new(opts.key, &context)
+              .map_err(|e| format!("Failed to parse key: {e}"))?,
+            context,
+            &opts.plaintext,
+          );
+        }
+        cli::beaufort::BeaufortCommands::Decipher(opts) => {
+          run_decipher(
+            &beaufort::BeaufortKey::try_new(opts.key, &context)
+              .map_err(|e| format!("Failed to parse key: {e}"))?,
+            context,
+            &opts.ciphertext,
+          );
+        }
+        cli::beaufort::BeaufortCommands::Dictionary(opts) => {
+          let (tx, candidate_collector_handle) =
+            spawn_candidate_collector(ciphertools_context()?);
+          let dictionary_iter =
+            get_dictionary_iter(opts.dictionary_file, context.clone())?;
+          run_dictionary_attack(context, opts.ciphertext, dictionary_iter, tx);
+
+          let _ = candidate_collector_handle.join();
+        }
+      }
+    }
+    cli::Commands::Gronsfeld(opts) => {
+      let context =
+        gronsfeld::Gronsfeld::new(opts.alphabet.into(), opts.skip_whitespace);
+      match opts.commands {
+        cli::gronsfeld::GronsfeldCommands::Encipher(opts) => {
+          run_encipher(
+            &gronsfeld::GronsfeldKey::try_new(&opts.key, &context)
+              .map_err(|e| format!("Failed to parse key: {e}"))?,
+            context,
+            &opts.plaintext,
+          );
+        }
+        cli::gronsfeld::GronsfeldCommands::Decipher(opts) => {
+          run_decipher(
+            &gronsfeld::GronsfeldKey::try_new(&opts.key, &context)
+              .map_err(|e| format!("Failed to parse key: {e}"))?,
+            context,
+            &opts.ciphertext,
+          );
+        }
+        cli::gronsfeld::GronsfeldCommands::Dictionary(opts) => {
+          let (tx, candidate_collector_handle) =
+            spawn_candidate_collector(ciphertools_context()?);
+          let dictionary_iter =
+            get_dictionary_iter(opts.dictionary_file, context.clone())?;
+          run_dictionary_attack(context, opts.ciphertext, dictionary_iter, tx);
+
+          let _ = candidate_collector_handle.join();
+        }
+      }
+    }
+    cli::Commands::Porta(opts) => {
+      let context =
+        porta::Porta::new(opts.alphabet.into(), opts.skip_whitespace);
+      match opts.commands {
+        cli::porta::PortaCommands::Encipher(opts) => {
+          run_encipher(
+            &porta::PortaKey::try_new(opts.key, &context)
+              .map_err(|e| format!("Failed to parse key: {e}"))?,
+            context,
+            &opts.plaintext,
+          );
+        }
+        cli::porta::PortaCommands::Decipher(opts) => {
+          run_decipher(
+            &porta::PortaKey::try_new(opts.key, &context)
+              .map_err(|e| format!("Failed to parse key: {e}"))?,
+            context,
+            &opts.ciphertext,
+          );
+        }
+        cli::porta::PortaCommands::Dictionary(opts) => {
+          let (tx, candidate_collector_handle) =
+            spawn_candidate_collector(ciphertools_context()?);
           let dictionary_iter =
             get_dictionary_iter(opts.dictionary_file, context.clone())?;
           run_dictionary_attack(context, opts.ciphertext, dictionary_iter, tx);
@@ -114,26 +261,104 @@ fn run(opts: CliOpts) -> Result<(), String> {
         }
       }
     }
+    cli::Commands::Quagmire(opts) => {
+      let context = quagmire::Quagmire::new(
+        opts.alphabet.into(),
+        opts.variant.into(),
+        opts.columnar,
+        opts.skip_whitespace,
+      );
+      match opts.commands {
+        cli::quagmire::QuagmireCommands::Encipher(opts) => {
+          run_encipher(
+            &quagmire::QuagmireKey::try_new(&opts.key, &context)
+              .map_err(|e| format!("Failed to parse key: {e}"))?,
+            context,
+            &opts.plaintext,
+          );
+        }
+        cli::quagmire::QuagmireCommands::Decipher(opts) => {
+          run_decipher(
+            &quagmire::QuagmireKey::try_new(&opts.key, &context)
+              .map_err(|e| format!("Failed to parse key: {e}"))?,
+            context,
+            &opts.ciphertext,
+          );
+        }
+      }
+    }
+    cli::Commands::RunningKey(opts) => {
+      let context = running_key::RunningKey::new(
+        opts.alphabet.into(),
+        opts.skip_whitespace,
+      );
+      match opts.commands {
+        cli::running_key::RunningKeyCommands::Encipher(opts) => {
+          let key = read_running_key(&opts.key_file, &context)?;
+          run_encipher(
+            &key.fragment(opts.offset, key.len()),
+            context,
+            &opts.plaintext,
+          );
+        }
+        cli::running_key::RunningKeyCommands::Decipher(opts) => {
+          let key = read_running_key(&opts.key_file, &context)?;
+          run_decipher(
+            &key.fragment(opts.offset, key.len()),
+            context,
+            &opts.ciphertext,
+          );
+        }
+        cli::running_key::RunningKeyCommands::Search(opts) => {
+          run_running_key_search(context, opts, ciphertools_context()?)?;
+        }
+        cli::running_key::RunningKeyCommands::Crib(opts) => {
+          run_running_key_crib(context, opts, ciphertools_context()?);
+        }
+      }
+    }
     cli::Commands::Substitution(opts) => {
       let context = substitution::Substitution::new(opts.alphabet.into());
       match opts.commands {
         cli::substitution::SubstitutionCommands::Encipher(opts) => {
-          let key = substitution::SubstitutionEncipherKey::try_from((
-            opts.key.as_str(),
-            &context,
-          ))
-          .map_err(|e| format!("Failed to parse key: {e}"))?;
+          let key = substitution_key(opts.key, &context)?;
           run_encipher(&key, context, &opts.plaintext);
         }
         cli::substitution::SubstitutionCommands::Decipher(opts) => {
-          let key = substitution::SubstitutionEncipherKey::try_from((
-            opts.key.as_str(),
-            &context,
-          ))
-          .map_err(|e| format!("Failed to parse key: {e}"))?
-          .into_decipher_key();
+          let key = substitution_key(opts.key, &context)?.into_decipher_key();
           run_decipher(&key, context, &opts.ciphertext);
         }
+        cli::substitution::SubstitutionCommands::Quipqiup(opts) => {
+          run_quipqiup(context, &opts.quipqiup_file, &opts.ciphertext)?;
+        }
+        cli::substitution::SubstitutionCommands::Solve(opts) => {
+          run_substitution_solve(context, opts, ciphertools_context()?);
+        }
+      }
+    }
+    cli::Commands::Homophonic(opts) => {
+      let context =
+        homophonic::Homophonic::new(opts.alphabet.into(), opts.width);
+      match opts.commands {
+        cli::homophonic::HomophonicCommands::Encipher(opts) => {
+          run_encipher(
+            &homophonic::HomophonicKey::try_new(&opts.key, &context)
+              .map_err(|e| format!("Failed to parse key: {e}"))?,
+            context,
+            &opts.plaintext,
+          );
+        }
+        cli::homophonic::HomophonicCommands::Decipher(opts) => {
+          run_decipher(
+            &homophonic::HomophonicKey::try_new(&opts.key, &context)
+              .map_err(|e| format!("Failed to parse key: {e}"))?,
+            context,
+            &opts.ciphertext,
+          );
+        }
+        cli::homophonic::HomophonicCommands::Solve(opts) => {
+          run_homophonic_solve(context, opts, ciphertools_context()?);
+        }
       }
     }
     cli::Commands::Caesar(opts) => {
@@ -161,12 +386,410 @@ fn run(opts: CliOpts) -> Result<(), String> {
             &opts.ciphertext,
             &mut Scoreboard::new(
               NonZeroUsize::new(10).unwrap(),
-              ciphertools_context.get_confidence,
+              ciphertools_context()?.get_confidence,
+            ),
+          );
+        }
+      }
+    }
+    cli::Commands::Affine(opts) => {
+      let context = affine::Affine::new(opts.alphabet.into());
+      match opts.commands {
+        cli::affine::AffineCommands::Encipher(opts) => {
+          let key = affine::AffineKey::try_from((opts.key.as_str(), &context))
+            .map_err(|e| format!("Failed to parse key: {e}"))?;
+          run_encipher(&key, context, &opts.plaintext);
+        }
+        cli::affine::AffineCommands::Decipher(opts) => {
+          let key = affine::AffineKey::try_from((opts.key.as_str(), &context))
+            .map_err(|e| format!("Failed to parse key: {e}"))?;
+          run_decipher(&key, context, &opts.ciphertext);
+        }
+        cli::affine::AffineCommands::BruteForce(opts) => {
+          run_brute_force(
+            context,
+            &opts.ciphertext,
+            &mut Scoreboard::new(
+              NonZeroUsize::new(10).unwrap(),
+              ciphertools_context()?.get_confidence,
+            ),
+          );
+        }
+      }
+    }
+    cli::Commands::Hill(opts) => {
+      let context = hill::Hill::new(opts.alphabet.into(), opts.padding)
+        .map_err(|e| format!("Invalid Hill padding: {e}"))?;
+      match opts.commands {
+        cli::hill::HillCommands::Encipher(opts) => {
+          let key = hill::HillKey::try_new(&opts.key, &context)
+            .map_err(|e| format!("Failed to parse key: {e}"))?;
+          run_encipher(&key, context, &opts.plaintext);
+        }
+        cli::hill::HillCommands::Decipher(opts) => {
+          let key = hill::HillKey::try_new(&opts.key, &context)
+            .map_err(|e| format!("Failed to parse key: {e}"))?;
+          run_decipher(&key, context, &opts.ciphertext);
+        }
+        cli::hill::HillCommands::BruteForce(opts) => {
+          run_brute_force(
+            context,
+            &opts.ciphertext,
+            &mut Scoreboard::new(
+              NonZeroUsize::new(10).unwrap(),
+              ciphertools_context()?.get_confidence,
+            ),
+          );
+        }
+        cli::hill::HillCommands::KnownPlaintext(opts) => {
+          if opts.size == 0 {
+            return Err("Key size must be at least 1".to_string());
+          }
+          let key = context
+            .known_plaintext_key(&opts.plaintext, &opts.ciphertext, opts.size)
+            .ok_or_else(|| {
+              format!(
+                "No {0} blocks of the known plaintext form an invertible \
+                 matrix, so they do not fix a {0}x{0} key",
+                opts.size
+              )
+            })?;
+          println!("Key: {key}");
+          run_decipher(&key, context, &opts.ciphertext);
+        }
+      }
+    }
+    cli::Commands::Columnar(opts) => {
+      let context = columnar::Columnar::new(
+        opts.alphabet.into(),
+        opts.skip_whitespace,
+        opts.padding,
+      );
+      match opts.commands {
+        cli::columnar::ColumnarCommands::Encipher(opts) => {
+          run_encipher(
+            &columnar::ColumnarKey::try_new(opts.key, &context)
+              .map_err(|e| format!("Failed to parse key: {e}"))?,
+            context,
+            &opts.plaintext,
+          );
+        }
+        cli::columnar::ColumnarCommands::Decipher(opts) => {
+          run_decipher(
+            &columnar::ColumnarKey::try_new(opts.key, &context)
+              .map_err(|e| format!("Failed to parse key: {e}"))?,
+            context,
+            &opts.ciphertext,
+          );
+        }
+        cli::columnar::ColumnarCommands::Dictionary(opts) => {
+          let (tx, candidate_collector_handle) =
+            spawn_candidate_collector(ciphertools_context()?);
+          let dictionary_iter =
+            get_dictionary_iter(opts.dictionary_file, context.clone())?;
+          run_dictionary_attack(context, opts.ciphertext, dictionary_iter, tx);
+
+          let _ = candidate_collector_handle.join();
+        }
+        cli::columnar::ColumnarCommands::Solve(opts) => {
+          run_columnar_solve(context, opts, ciphertools_context()?);
+        }
+      }
+    }
+    cli::Commands::RailFence(opts) => {
+      let context =
+        rail_fence::RailFence::new(opts.skip_whitespace, opts.max_rails);
+      match opts.commands {
+        cli::rail_fence::RailFenceCommands::Encipher(opts) => {
+          let key =
+            rail_fence::RailFenceKey::try_from((opts.key.as_str(), &context))
+              .map_err(|e| format!("Failed to parse key: {e}"))?;
+          run_encipher(&key, context, &opts.plaintext);
+        }
+        cli::rail_fence::RailFenceCommands::Decipher(opts) => {
+          let key =
+            rail_fence::RailFenceKey::try_from((opts.key.as_str(), &context))
+              .map_err(|e| format!("Failed to parse key: {e}"))?;
+          run_decipher(&key, context, &opts.ciphertext);
+        }
+        cli::rail_fence::RailFenceCommands::BruteForce(opts) => {
+          run_brute_force(
+            context,
+            &opts.ciphertext,
+            &mut Scoreboard::new(
+              NonZeroUsize::new(10).unwrap(),
+              ciphertools_context()?.get_confidence,
             ),
           );
         }
       }
     }
+    cli::Commands::Route(opts) => {
+      let context = route::Route::new(opts.skip_whitespace, opts.max_width);
+      match opts.commands {
+        cli::route::RouteCommands::Encipher(opts) => {
+          let key = route::RouteKey::try_from((opts.key.as_str(), &context))
+            .map_err(|e| format!("Failed to parse key: {e}"))?;
+          run_encipher(&key, context, &opts.plaintext);
+        }
+        cli::route::RouteCommands::Decipher(opts) => {
+          let key = route::RouteKey::try_from((opts.key.as_str(), &context))
+            .map_err(|e| format!("Failed to parse key: {e}"))?;
+          run_decipher(&key, context, &opts.ciphertext);
+        }
+        cli::route::RouteCommands::BruteForce(opts) => {
+          run_brute_force(
+            context,
+            &opts.ciphertext,
+            &mut Scoreboard::new(
+              NonZeroUsize::new(10).unwrap(),
+              ciphertools_context()?.get_confidence,
+            ),
+          );
+        }
+      }
+    }
+    cli::Commands::Playfair(opts) => {
+      let merge = cli::parse_merge(&opts.merge)?;
+      let context =
+        playfair::Playfair::new(opts.alphabet.into(), merge, opts.filler);
+      // Fails early when the alphabet does not fit a square.
+      playfair::PlayfairKey::try_new("", &context)
+        .map_err(|e| format!("Invalid Playfair alphabet: {e}"))?;
+      match opts.commands {
+        cli::playfair::PlayfairCommands::Encipher(opts) => {
+          let key = playfair::PlayfairKey::try_new(&opts.key, &context)
+            .map_err(|e| format!("Failed to parse key: {e}"))?;
+          run_encipher(&key, context, &opts.plaintext);
+        }
+        cli::playfair::PlayfairCommands::Decipher(opts) => {
+          let key = playfair::PlayfairKey::try_new(&opts.key, &context)
+            .map_err(|e| format!("Failed to parse key: {e}"))?;
+          run_decipher(&key, context, &opts.ciphertext);
+        }
+        cli::playfair::PlayfairCommands::Dictionary(opts) => {
+          let (tx, candidate_collector_handle) =
+            spawn_candidate_collector(ciphertools_context()?);
+          let dictionary_iter =
+            get_dictionary_iter(opts.dictionary_file, context.clone())?;
+          run_dictionary_attack(context, opts.ciphertext, dictionary_iter, tx);
+
+          let _ = candidate_collector_handle.join();
+        }
+        cli::playfair::PlayfairCommands::Solve(opts) => {
+          run_anneal_solve(
+            context,
+            opts.ciphertext,
+            opts.anneal.restarts,
+            AnnealOptions::from(&opts.anneal),
+            ciphertools_context()?,
+            |context, rng| Some(context.random_key(rng)),
+            playfair::Playfair::hill_climb,
+          );
+        }
+      }
+    }
+    cli::Commands::TwoSquare(opts) => {
+      let merge = cli::parse_merge(&opts.merge)?;
+      let context = two_square::TwoSquare::new(
+        opts.alphabet.into(),
+        merge,
+        opts.filler,
+        if opts.vertical {
+          two_square::Orientation::Vertical
+        } else {
+          two_square::Orientation::Horizontal
+        },
+      );
+      // Fails early when the alphabet does not fit a square.
+      two_square::TwoSquareKey::try_new("", &context)
+        .map_err(|e| format!("Invalid TwoSquare alphabet: {e}"))?;
+      match opts.commands {
+        cli::two_square::TwoSquareCommands::Encipher(opts) => {
+          let key = two_square::TwoSquareKey::try_new(&opts.key, &context)
+            .map_err(|e| format!("Failed to parse key: {e}"))?;
+          run_encipher(&key, context, &opts.plaintext);
+        }
+        cli::two_square::TwoSquareCommands::Decipher(opts) => {
+          let key = two_square::TwoSquareKey::try_new(&opts.key, &context)
+            .map_err(|e| format!("Failed to parse key: {e}"))?;
+          run_decipher(&key, context, &opts.ciphertext);
+        }
+        cli::two_square::TwoSquareCommands::Dictionary(opts) => {
+          let (tx, candidate_collector_handle) =
+            spawn_candidate_collector(ciphertools_context()?);
+          let dictionary_iter =
+            get_dictionary_iter(opts.dictionary_file, context.clone())?;
+          run_dictionary_attack(context, opts.ciphertext, dictionary_iter, tx);
+
+          let _ = candidate_collector_handle.join();
+        }
+        cli::two_square::TwoSquareCommands::Solve(opts) => {
+          run_anneal_solve(
+            context,
+            opts.ciphertext,
+            opts.anneal.restarts,
+            AnnealOptions::from(&opts.anneal),
+            ciphertools_context()?,
+            |context, rng| context.random_key(rng).ok(),
+            two_square::TwoSquare::hill_climb,
+          );
+        }
+      }
+    }
+    cli::Commands::Bifid(opts) => {
+      let merge = cli::parse_merge(&opts.merge)?;
+      let context = bifid::Bifid::new(opts.alphabet.into(), merge, opts.period);
+      // Fails early when the alphabet does not fit a square.
+      bifid::BifidKey::try_new("", &context)
+        .map_err(|e| format!("Invalid Bifid alphabet: {e}"))?;
+      match opts.commands {
+        cli::bifid::BifidCommands::Encipher(opts) => {
+          let key = bifid::BifidKey::try_new(&opts.key, &context)
+            .map_err(|e| format!("Failed to parse key: {e}"))?;
+          run_encipher(&key, context, &opts.plaintext);
+        }
+        cli::bifid::BifidCommands::Decipher(opts) => {
+          let key = bifid::BifidKey::try_new(&opts.key, &context)
+            .map_err(|e| format!("Failed to parse key: {e}"))?;
+          run_decipher(&key, context, &opts.ciphertext);
+        }
+        cli::bifid::BifidCommands::Dictionary(opts) => {
+          let (tx, candidate_collector_handle) =
+            spawn_candidate_collector(ciphertools_context()?);
+          let dictionary_iter =
+            get_dictionary_iter(opts.dictionary_file, context.clone())?;
+          run_dictionary_attack(context, opts.ciphertext, dictionary_iter, tx);
+
+          let _ = candidate_collector_handle.join();
+        }
+        cli::bifid::BifidCommands::Period(opts) => {
+          run_period(context.periods(&opts.ciphertext, opts.max_period));
+        }
+      }
+    }
+    cli::Commands::Trifid(opts) => {
+      let alphabet: alphabet::Alphabet = opts.alphabet.into();
+      let context = trifid::Trifid::new(
+        alphabet.iter().chain([opts.filler]).collect(),
+        opts.period,
+      );
+      // Fails early when the alphabet does not fill a cube.
+      trifid::TrifidKey::try_new("", &context)
+        .map_err(|e| format!("Invalid Trifid alphabet: {e}"))?;
+      match opts.commands {
+        cli::trifid::TrifidCommands::Encipher(opts) => {
+          let key = trifid::TrifidKey::try_new(&opts.key, &context)
+            .map_err(|e| format!("Failed to parse key: {e}"))?;
+          run_encipher(&key, context, &opts.plaintext);
+        }
+        cli::trifid::TrifidCommands::Decipher(opts) => {
+          let key = trifid::TrifidKey::try_new(&opts.key, &context)
+            .map_err(|e| format!("Failed to parse key: {e}"))?;
+          run_decipher(&key, context, &opts.ciphertext);
+        }
+        cli::trifid::TrifidCommands::Dictionary(opts) => {
+          let (tx, candidate_collector_handle) =
+            spawn_candidate_collector(ciphertools_context()?);
+          let dictionary_iter =
+            get_dictionary_iter(opts.dictionary_file, context.clone())?;
+          run_dictionary_attack(context, opts.ciphertext, dictionary_iter, tx);
+
+          let _ = candidate_collector_handle.join();
+        }
+        cli::trifid::TrifidCommands::Period(opts) => {
+          run_period(context.periods(&opts.ciphertext, opts.max_period));
+        }
+      }
+    }
+    cli::Commands::FourSquare(opts) => {
+      let merge = cli::parse_merge(&opts.merge)?;
+      let context =
+        four_square::FourSquare::new(opts.alphabet.into(), merge, opts.filler);
+      // Fails early when the alphabet does not fit a square.
+      four_square::FourSquareKey::try_new("", &context)
+        .map_err(|e| format!("Invalid FourSquare alphabet: {e}"))?;
+      match opts.commands {
+        cli::four_square::FourSquareCommands::Encipher(opts) => {
+          let key = four_square::FourSquareKey::try_new(&opts.key, &context)
+            .map_err(|e| format!("Failed to parse key: {e}"))?;
+          run_encipher(&key, context, &opts.plaintext);
+        }
+        cli::four_square::FourSquareCommands::Decipher(opts) => {
+          let key = four_square::FourSquareKey::try_new(&opts.key, &context)
+            .map_err(|e| format!("Failed to parse key: {e}"))?;
+          run_decipher(&key, context, &opts.ciphertext);
+        }
+        cli::four_square::FourSquareCommands::Dictionary(opts) => {
+          let (tx, candidate_collector_handle) =
+            spawn_candidate_collector(ciphertools_context()?);
+          let dictionary_iter =
+            get_dictionary_iter(opts.dictionary_file, context.clone())?;
+          run_dictionary_attack(context, opts.ciphertext, dictionary_iter, tx);
+
+          let _ = candidate_collector_handle.join();
+        }
+        cli::four_square::FourSquareCommands::Solve(opts) => {
+          run_anneal_solve(
+            context,
+            opts.ciphertext,
+            opts.anneal.restarts,
+            AnnealOptions::from(&opts.anneal),
+            ciphertools_context()?,
+            |context, rng| context.random_key(rng).ok(),
+            four_square::FourSquare::hill_climb,
+          );
+        }
+      }
+    }
+    cli::Commands::Adfgx(opts) => {
+      let alphabet: alphabet::Alphabet = opts.alphabet.into();
+      let context = if opts.adfgvx {
+        adfgx::Adfgx::new(alphabet.iter().chain('0'..='9').collect(), None)
+      } else {
+        adfgx::Adfgx::new(alphabet, cli::parse_merge(&opts.merge)?)
+      };
+      // Fails early when the alphabet does not fit a square with labels.
+      context
+        .square("")
+        .map_err(|e| format!("Invalid ADFGX alphabet: {e}"))?;
+      match opts.commands {
+        cli::adfgx::AdfgxCommands::Encipher(opts) => {
+          let key = adfgx::AdfgxKey::try_new(&opts.key, &context)
+            .map_err(|e| format!("Failed to parse key: {e}"))?;
+          run_encipher(&key, context, &opts.plaintext);
+        }
+        cli::adfgx::AdfgxCommands::Decipher(opts) => {
+          let key = adfgx::AdfgxKey::try_new(&opts.key, &context)
+            .map_err(|e| format!("Failed to parse key: {e}"))?;
+          run_decipher(&key, context, &opts.ciphertext);
+        }
+        cli::adfgx::AdfgxCommands::Solve(opts) => {
+          run_adfgx_solve(context, opts, ciphertools_context()?);
+        }
+      }
+    }
+    cli::Commands::Enigma(opts) => {
+      let context = enigma::Enigma::new();
+      match opts.commands {
+        cli::enigma::EnigmaCommands::Encipher(opts) => {
+          let key = enigma::EnigmaKey::try_new(&opts.key, &context)
+            .map_err(|e| format!("Failed to parse key: {e}"))?;
+          run_encipher(&key, context, &opts.plaintext);
+        }
+        cli::enigma::EnigmaCommands::Decipher(opts) => {
+          let key = enigma::EnigmaKey::try_new(&opts.key, &context)
+            .map_err(|e| format!("Failed to parse key: {e}"))?;
+          run_decipher(&key, context, &opts.ciphertext);
+        }
+        cli::enigma::EnigmaCommands::Solve(opts) => {
+          run_enigma_solve(context, opts, ciphertools_context()?)?;
+        }
+      }
+    }
+    cli::Commands::Identify(opts) => {
+      run_identify(opts, &ciphertools_context()?.language);
+    }
   }
   Ok(())
 }
@@ -181,6 +804,593 @@ fn run_decipher<D: Decipher>(key: &D::Key, context: D, ciphertext: &str) {
   println!("{}", result);
 }
 
+fn run_autokey_solve(
+  context: autokey::Autokey,
+  opts: cli::autokey::AutokeySolveOpts,
+  ciphertools_context: CiphertoolsContext,
+) {
+  let CiphertoolsContext {
+    language,
+    get_confidence,
+    pool,
+  } = ciphertools_context;
+  let scoreboard =
+    Scoreboard::new(NonZeroUsize::new(10).unwrap(), get_confidence.clone());
+  let options = AnnealOptions::from(&opts.anneal);
+  let ciphertext = opts.ciphertext;
+
+  pool.install(|| {
+    (1..=opts.max_primer_length)
+      .into_par_iter()
+      .for_each(|primer_length| {
+        let key = context.frequency_key(&ciphertext, primer_length, &language);
+        scoreboard.insert(context.decipher(&ciphertext, &key), key.to_string());
+
+        if !opts.refine {
+          return;
+        }
+
+        (0..opts.anneal.restarts).into_par_iter().for_each(|_| {
+          let (key, confidence) = context.hill_climb(
+            &ciphertext,
+            key.clone(),
+            &get_confidence,
+            &options,
+            &mut rand::rng(),
+          );
+          scoreboard.insert_with_confidence(
+            context.decipher(&ciphertext, &key),
+            key.to_string(),
+            confidence,
+          );
+        });
+      });
+  });
+
+  scoreboard.display_scoreboard();
+}
+
+fn run_identify(opts: cli::identify::IdentifyOpts, language: &Language) {
+  let alphabet = opts.alphabet.into();
+  let features = Features::new(&opts.ciphertext, &alphabet, language);
+
+  println!(
+    "Letters: {} ({})",
+    features.letters,
+    if features.letters.is_multiple_of(2) {
+      "even"
+    } else {
+      "odd"
+    }
+  );
+  println!("Symbols: {}", features.symbols);
+  println!("Doubled letters: {}", features.doubled_letters);
+  println!("IoC: {:.4}", features.index_of_coincidence);
+  println!(
+    "Periodic IoC: {:.4} (period {})",
+    features.periodic_index_of_coincidence, features.period
+  );
+  println!("Chi2: {:.4}", features.chi2);
+  println!("Best Caesar shift chi2: {:.4}", features.best_shift_chi2);
+
+  features.rank(&alphabet, language).into_iter().for_each(
+    |(cipher_type, score)| println!("Score: {score:.4}, Cipher: {cipher_type}"),
+  );
+}
+
+fn run_key_length(
+  context: vigenere::Vigenere,
+  opts: cli::vigenere::VigenereKeyLengthOpts,
+  language: &Language,
+) {
+  match context.friedman_estimate(&opts.ciphertext, language) {
+    Some(estimate) => println!("Friedman estimate: {estimate:.2}"),
+    None => println!("Friedman estimate: n/a"),
+  }
+
+  context
+    .key_lengths(&opts.ciphertext, language, opts.max_key_length)
+    .into_iter()
+    .take(10)
+    .for_each(|candidate| {
+      println!(
+        "Score: {:.4}, Key length: {}, IoC: {:.4}, Kasiski: {:.2}",
+        candidate.score,
+        candidate.key_length,
+        candidate.index_of_coincidence,
+        candidate.kasiski
+      )
+    });
+}
+
+fn run_period(candidates: Vec<PeriodCandidate>) {
+  candidates.into_iter().take(10).for_each(|candidate| {
+    println!(
+      "Score: {:.4}, Period: {}, IoC: {:.5}",
+      candidate.score, candidate.period, candidate.index_of_coincidence
+    )
+  });
+}
+
+fn run_vigenere_solve(
+  context: vigenere::Vigenere,
+  opts: cli::vigenere::VigenereSolveOpts,
+  ciphertools_context: CiphertoolsContext,
+) {
+  let CiphertoolsContext {
+    language,
+    get_confidence,
+    pool,
+  } = ciphertools_context;
+  let scoreboard =
+    Scoreboard::new(NonZeroUsize::new(10).unwrap(), get_confidence.clone());
+  let options = AnnealOptions::from(&opts.anneal);
+  let ciphertext = opts.ciphertext;
+
+  let key_lengths: Vec<_> = context
+    .key_lengths(&ciphertext, &language, opts.max_key_length)
+    .into_iter()
+    .take(opts.key_lengths)
+    .map(|candidate| candidate.key_length)
+    .collect();
+
+  pool.install(|| {
+    key_lengths.into_par_iter().for_each(|key_length| {
+      let key = context.frequency_key(&ciphertext, key_length, &language);
+      scoreboard.insert(context.decipher(&ciphertext, &key), key.to_string());
+
+      if !opts.refine {
+        return;
+      }
+
+      (0..opts.anneal.restarts).into_par_iter().for_each(|_| {
+        let (key, confidence) = context.hill_climb(
+          &ciphertext,
+          key.clone(),
+          &get_confidence,
+          &options,
+          &mut rand::rng(),
+        );
+        scoreboard.insert_with_confidence(
+          context.decipher(&ciphertext, &key),
+          key.to_string(),
+          confidence,
+        );
+      });
+    });
+  });
+
+  scoreboard.display_scoreboard();
+}
+
+fn run_columnar_solve(
+  context: columnar::Columnar,
+  opts: cli::columnar::ColumnarSolveOpts,
+  ciphertools_context: CiphertoolsContext,
+) {
+  let CiphertoolsContext {
+    get_confidence,
+    pool,
+    ..
+  } = ciphertools_context;
+  let scoreboard =
+    Scoreboard::new(NonZeroUsize::new(10).unwrap(), get_confidence.clone());
+  let options = AnnealOptions::from(&opts.anneal);
+  let ciphertext = opts.ciphertext;
+
+  pool.install(|| {
+    (opts.min_width..=opts.max_width).for_each(|width| {
+      if width <= opts.exhaustive_width {
+        context.keys_of_width(width).par_bridge().for_each(|key| {
+          scoreboard
+            .insert(context.decipher(&ciphertext, &key), key.to_string());
+        });
+        return;
+      }
+
+      (0..opts.anneal.restarts).into_par_iter().for_each(|_| {
+        let mut rng = rand::rng();
+        let key = context.random_key(width, &mut rng);
+        let (key, confidence) = context.hill_climb(
+          &ciphertext,
+          key,
+          &get_confidence,
+          &options,
+          &mut rng,
+        );
+        scoreboard.insert_with_confidence(
+          context.decipher(&ciphertext, &key),
+          key.to_string(),
+          confidence,
+        );
+      });
+    });
+  });
+
+  scoreboard.display_scoreboard();
+}
+
+/// Anneals `restarts` random keys in parallel, for the ciphers whose solver
+/// only needs a random starting key and a hill climb from it.
+fn run_anneal_solve<D>(
+  context: D,
+  ciphertext: String,
+  restarts: usize,
+  options: AnnealOptions,
+  ciphertools_context: CiphertoolsContext,
+  random_key: impl Fn(&D, &mut rand::rngs::ThreadRng) -> Option<D::Key> + Sync,
+  hill_climb: impl Fn(
+    &D,
+    &str,
+    D::Key,
+    &GetConfidence,
+    &AnnealOptions,
+    &mut rand::rngs::ThreadRng,
+  ) -> (D::Key, f64)
+  + Sync,
+) where
+  D: Decipher + Sync,
+  D::Key: Display,
+{
+  let CiphertoolsContext {
+    get_confidence,
+    pool,
+    ..
+  } = ciphertools_context;
+  let scoreboard =
+    Scoreboard::new(NonZeroUsize::new(10).unwrap(), get_confidence.clone());
+
+  pool.install(|| {
+    (0..restarts).into_par_iter().for_each(|_| {
+      let mut rng = rand::rng();
+      let Some(key) = random_key(&context, &mut rng) else {
+        return;
+      };
+      let (key, confidence) = hill_climb(
+        &context,
+        &ciphertext,
+        key,
+        &get_confidence,
+        &options,
+        &mut rng,
+      );
+      scoreboard.insert_with_confidence(
+        context.decipher(&ciphertext, &key),
+        key.to_string(),
+        confidence,
+      );
+    });
+  });
+
+  scoreboard.display_scoreboard();
+}
+
+fn run_adfgx_solve(
+  context: adfgx::Adfgx,
+  opts: cli::adfgx::AdfgxSolveOpts,
+  ciphertools_context: CiphertoolsContext,
+) {
+  let CiphertoolsContext {
+    language,
+    get_confidence,
+    pool,
+  } = ciphertools_context;
+  let scoreboard =
+    Scoreboard::new(NonZeroUsize::new(10).unwrap(), get_confidence.clone());
+  let transposition_options = AnnealOptions {
+    iterations: opts.transposition_iterations,
+    temperature: 0.0,
+  };
+  let options = AnnealOptions::from(&opts.anneal);
+  let ciphertext = opts.ciphertext;
+
+  pool.install(|| {
+    // The transposition with the most language-like label pairs for every
+    // width, then the square for each transposition pairing labels the same.
+    let transpositions: Vec<_> = (opts.min_width..=opts.max_width)
+      .into_par_iter()
+      .filter_map(|width| {
+        (0..opts.transposition_restarts)
+          .map(|_| {
+            let mut rng = rand::rng();
+            let key = context.random_transposition(width, &mut rng);
+            context.transposition_hill_climb(
+              &ciphertext,
+              key,
+              &transposition_options,
+              &mut rng,
+            )
+          })
+          .max_by(|a, b| a.1.total_cmp(&b.1))
+          .map(|(key, _)| key)
+      })
+      .flat_map(|key| context.equivalent_transpositions(&key))
+      .collect();
+
+    transpositions
+      .into_par_iter()
+      .flat_map(|transposition| {
+        (0..opts.anneal.restarts)
+          .into_par_iter()
+          .map(move |_| transposition.clone())
+      })
+      .for_each(|transposition| {
+        let mut rng = rand::rng();
+        let Ok(key) =
+          context.frequency_key(&ciphertext, transposition, &language)
+        else {
+          return;
+        };
+        let (key, confidence) = context.square_hill_climb(
+          &ciphertext,
+          key,
+          &get_confidence,
+          &options,
+          &mut rng,
+        );
+        scoreboard.insert_with_confidence(
+          context.decipher(&ciphertext, &key),
+          key.to_string(),
+          confidence,
+        );
+      });
+  });
+
+  scoreboard.display_scoreboard();
+}
+
+fn run_enigma_solve(
+  context: enigma::Enigma,
+  opts: cli::enigma::EnigmaSolveOpts,
+  ciphertools_context: CiphertoolsContext,
+) -> Result<(), String> {
+  let rotors = opts
+    .rotors
+    .split(',')
+    .map(|rotor| {
+      enigma::Rotor::from_name(rotor.trim())
+        .ok_or_else(|| format!("Invalid rotor: '{rotor}'"))
+    })
+    .collect::<Result<Vec<_>, _>>()?;
+  let reflector = enigma::Reflector::from_name(&opts.reflector)
+    .ok_or_else(|| format!("Invalid reflector: '{}'", opts.reflector))?;
+  if reflector.is_thin() {
+    return Err("The search covers three-rotor machines only".to_string());
+  }
+
+  let pool = Arc::clone(&ciphertools_context.pool);
+  let get_confidence = ciphertools_context.get_confidence.clone();
+  let (tx, candidate_collector_handle) =
+    spawn_candidate_collector(ciphertools_context);
+  let options = enigma::SolveOptions {
+    candidates: opts.candidates,
+    finalists: opts.finalists,
+    restarts: opts.anneal.restarts,
+    anneal: AnnealOptions::from(&opts.anneal),
+  };
+  let ciphertext = opts.ciphertext;
+
+  pool.install(|| {
+    context.solve(
+      &ciphertext,
+      reflector,
+      &context.rotor_orders(&rotors),
+      &get_confidence,
+      &options,
+      &mut rand::rng(),
+      |key, _| {
+        let text = context.decipher(&ciphertext, &key);
+        let _ = tx.send(CandidateCollectorMsg::CandidatePlaintext {
+          text,
+          key: key.to_string(),
+        });
+      },
+    );
+  });
+  drop(tx);
+
+  let _ = candidate_collector_handle.join();
+
+  Ok(())
+}
+
+fn substitution_key(
+  opts: cli::substitution::SubstitutionKeyOpts,
+  context: &substitution::Substitution,
+) -> Result<substitution::SubstitutionEncipherKey, String> {
+  match (opts.keyword, opts.key) {
+    (Some(keyword), _) => substitution::SubstitutionEncipherKey::from_keyword(
+      &keyword,
+      opts.columnar,
+      context,
+    ),
+    (None, key) => substitution::SubstitutionEncipherKey::try_from((
+      key.unwrap_or_default().as_str(),
+      context,
+    )),
+  }
+  .map_err(|e| format!("Failed to parse key: {e}"))
+}
+
+fn run_quipqiup(
+  context: substitution::Substitution,
+  quipqiup_file: &str,
+  ciphertext: &str,
+) -> Result<(), String> {
+  let quipqiup_json = std::fs::read_to_string(quipqiup_file)
+    .map_err(|e| format!("Failed to open file '{}': {}", quipqiup_file, e))?;
+  let quipqiup_context: QuipqiupContext = serde_json::from_str(&quipqiup_json)
+    .map_err(|e| format!("Failed to parse '{}': {}", quipqiup_file, e))?;
+
+  for solution in context.quipqiup(&quipqiup_context, ciphertext, 10) {
+    println!(
+      "Matched letters: {}, Key: {}, Text: {}",
+      solution.matched_letters,
+      solution.key,
+      context.partial_decipher(ciphertext, &solution.key)
+    );
+  }
+
+  Ok(())
+}
+
+fn run_substitution_solve(
+  context: substitution::Substitution,
+  opts: cli::substitution::SubstitutionSolveOpts,
+  ciphertools_context: CiphertoolsContext,
+) {
+  let CiphertoolsContext {
+    language,
+    get_confidence,
+    pool,
+  } = ciphertools_context;
+  let scoreboard =
+    Scoreboard::new(NonZeroUsize::new(10).unwrap(), get_confidence.clone());
+  let options = AnnealOptions::from(&opts.anneal);
+  let ciphertext = opts.ciphertext;
+
+  pool.install(|| {
+    (0..opts.anneal.restarts).into_par_iter().for_each(|_| {
+      let mut rng = rand::rng();
+      let key = match opts.seed {
+        cli::substitution::Seed::Random => context.random_key(&mut rng),
+        cli::substitution::Seed::Frequency => {
+          context.frequency_key(&ciphertext, &language)
+        }
+      };
+      let (key, confidence) = context.hill_climb(
+        &ciphertext,
+        key,
+        &get_confidence,
+        &options,
+        &mut rng,
+      );
+      scoreboard.insert_with_confidence(
+        context.decipher(&ciphertext, &key),
+        context.key_string(&key),
+        confidence,
+      );
+    });
+  });
+
+  scoreboard.display_scoreboard();
+}
+
+fn run_homophonic_solve(
+  context: homophonic::Homophonic,
+  opts: cli::homophonic::HomophonicSolveOpts,
+  ciphertools_context: CiphertoolsContext,
+) {
+  let CiphertoolsContext {
+    language,
+    get_confidence,
+    pool,
+  } = ciphertools_context;
+  let scoreboard =
+    Scoreboard::new(NonZeroUsize::new(10).unwrap(), get_confidence.clone());
+  let options = AnnealOptions::from(&opts.anneal);
+  let ciphertext = opts.ciphertext;
+
+  pool.install(|| {
+    (0..opts.anneal.restarts).into_par_iter().for_each(|_| {
+      let mut rng = rand::rng();
+      let key = match opts.seed {
+        cli::homophonic::Seed::Random => {
+          context.random_key(&ciphertext, &mut rng)
+        }
+        cli::homophonic::Seed::Frequency => {
+          context.frequency_key(&ciphertext, &language)
+        }
+      };
+      let (key, confidence) = context.hill_climb(
+        &ciphertext,
+        key,
+        &get_confidence,
+        &language,
+        &options,
+        &mut rng,
+      );
+      scoreboard.insert_with_confidence(
+        context.decipher(&ciphertext, &key),
+        key.to_string(),
+        confidence,
+      );
+    });
+  });
+
+  scoreboard.display_scoreboard();
+}
+
+fn read_running_key(
+  key_file: &str,
+  context: &running_key::RunningKey,
+) -> Result<running_key::RunningKeyKey, String> {
+  let text = std::fs::read_to_string(key_file)
+    .map_err(|e| format!("Failed to open file '{}': {}", key_file, e))?;
+
+  Ok(running_key::RunningKeyKey::from_text(&text, context))
+}
+
+fn run_running_key_search(
+  context: running_key::RunningKey,
+  opts: cli::running_key::RunningKeySearchOpts,
+  ciphertools_context: CiphertoolsContext,
+) -> Result<(), String> {
+  let CiphertoolsContext {
+    get_confidence,
+    pool,
+    ..
+  } = ciphertools_context;
+  let book = read_running_key(&opts.book_file, &context)?;
+  let key_len = context.key_len(&opts.ciphertext);
+  if book.len() < key_len {
+    return Err(format!(
+      "'{}' has {} letters, the ciphertext needs {key_len}",
+      opts.book_file,
+      book.len()
+    ));
+  }
+  let scoreboard =
+    Scoreboard::new(NonZeroUsize::new(10).unwrap(), get_confidence.clone());
+
+  let offsets = pool.install(|| {
+    context.search_offsets(&opts.ciphertext, &book, &get_confidence)
+  });
+  for (offset, confidence) in offsets.into_iter().take(10) {
+    let key = book.fragment(offset, key_len);
+    scoreboard.insert_with_confidence(
+      context.decipher(&opts.ciphertext, &key),
+      format!("{offset} {key}"),
+      confidence,
+    );
+  }
+
+  scoreboard.display_scoreboard();
+
+  Ok(())
+}
+
+fn run_running_key_crib(
+  context: running_key::RunningKey,
+  opts: cli::running_key::RunningKeyCribOpts,
+  ciphertools_context: CiphertoolsContext,
+) {
+  context
+    .crib_drag(
+      &opts.ciphertext,
+      &opts.crib,
+      &ciphertools_context.get_confidence,
+    )
+    .into_iter()
+    .take(opts.candidates)
+    .for_each(|candidate| {
+      println!(
+        "Confidence: {:.4}, Position: {}, Fragment: {}",
+        candidate.confidence, candidate.position, candidate.fragment
+      )
+    });
+}
+
 fn run_brute_force<D>(context: D, ciphertext: &str, scoreboard: &mut Scoreboard)
 where
   D: KeysIterator + Decipher,
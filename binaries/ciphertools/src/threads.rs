@@ -26,6 +26,7 @@ fn candidate_collector(
   let CiphertoolsContext {
     get_confidence,
     pool,
+    ..
   } = ciphertools_context;
 
   let scoreboard = Arc::new(Scoreboard::new(
@@ -33,7 +34,9 @@ fn candidate_collector(
     get_confidence.clone(),
   ));
 
-  pool.scope(|s| {
+  // Receive on this thread rather than a worker, so the pool stays free for
+  // the searches producing the candidates.
+  pool.in_place_scope(|s| {
     rx.iter().for_each(|msg| {
       let scoreboard = Arc::clone(&scoreboard);
       let get_confidence = get_confidence.clone();
@@ -1,5 +1,7 @@
 use ahash::AHashMap;
+use itertools::Itertools;
 use serde::{Deserialize, Serialize};
+use std::fmt;
 
 static LATIN: &[char; 26] = &[
   'a', 'b', 'c', 'd', 'e', 'f', 'g', 'h', 'i', 'j', 'k', 'l', 'm', 'n', 'o',
@@ -37,6 +39,19 @@ enum AlphabetDeserializer {
   Greek,
 }
 
+#[derive(Debug)]
+pub enum ParseError {
+  InvalidChar(char),
+}
+
+impl fmt::Display for ParseError {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    match self {
+      ParseError::InvalidChar(c) => write!(f, "Invalid character: '{c}'"),
+    }
+  }
+}
+
 impl From<AlphabetDeserializer> for Alphabet {
   fn from(value: AlphabetDeserializer) -> Self {
     match value {
@@ -70,6 +85,43 @@ impl Alphabet {
   pub fn iter(&self) -> impl Iterator<Item = char> + use<'_> {
     self.chars.iter().copied()
   }
+
+  /// Mixes the alphabet with `keyword`: its letters first, without
+  /// repeats, then the rest of the alphabet in order. When `columnar`, that
+  /// sequence is written in rows as wide as the keyword and read off by
+  /// columns, taken in the alphabet order of the keyword letters heading
+  /// them.
+  pub fn keyword_mixed(
+    &self,
+    keyword: &str,
+    columnar: bool,
+  ) -> Result<Alphabet, ParseError> {
+    if let Some(c) = keyword.chars().find(|&c| !self.contains(c)) {
+      return Err(ParseError::InvalidChar(c));
+    }
+
+    let mut chars: Vec<char> = Vec::with_capacity(self.len());
+    keyword.chars().chain(self.iter()).for_each(|c| {
+      if !chars.contains(&c) {
+        chars.push(c);
+      }
+    });
+
+    let width = keyword.chars().unique().count();
+    if !columnar || width == 0 {
+      return Ok(Alphabet::from_iter(chars));
+    }
+
+    let mut columns: Vec<usize> = (0..width).collect();
+    columns.sort_by_key(|&column| self.get_index(chars[column]));
+
+    Ok(
+      columns
+        .into_iter()
+        .flat_map(|column| chars.iter().skip(column).step_by(width).copied())
+        .collect(),
+    )
+  }
 }
 
 impl Default for Alphabet {
@@ -0,0 +1,194 @@
+mod solve;
+
+use crate::{
+  alphabet::Alphabet,
+  cipher::{
+    Decipher, Encipher,
+    columnar::{self, Columnar, ColumnarKey},
+  },
+  polybius::{self, PolybiusSquare},
+};
+use cipher_derive::{IntoDecipherKey, IntoEncipherKey};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+// Row and column labels of the 5x5 and 6x6 squares.
+const ADFGX: [char; 5] = ['a', 'd', 'f', 'g', 'x'];
+const ADFGVX: [char; 6] = ['a', 'd', 'f', 'g', 'v', 'x'];
+
+/// A Polybius square for the substitution and a keyword for the columnar
+/// transposition of its labels.
+#[derive(
+  Debug, Clone, Serialize, Deserialize, IntoDecipherKey, IntoEncipherKey,
+)]
+pub struct AdfgxKey {
+  square: PolybiusSquare,
+  transposition: ColumnarKey,
+}
+
+/// Replaces every letter with the labels of its row and column in a keyed
+/// square, then transposes the labels by columns. A 5x5 square is labelled
+/// ADFGX and a 6x6 square, e.g. Latin letters and digits, ADFGVX. Letters
+/// not in the square are dropped.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Adfgx {
+  alphabet: Alphabet,
+  merge: Option<(char, char)>,
+  columnar: Columnar,
+}
+
+#[derive(Debug)]
+pub enum ParseError {
+  InvalidFormat,
+  Square(polybius::ParseError),
+  Transposition(columnar::ParseError),
+  UnsupportedSide(usize),
+}
+
+impl fmt::Display for ParseError {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    match self {
+      ParseError::InvalidFormat => {
+        write!(f, "Expected a square keyword and a transposition keyword")
+      }
+      ParseError::Square(e) => write!(f, "Invalid square: {e}"),
+      ParseError::Transposition(e) => write!(f, "Invalid transposition: {e}"),
+      ParseError::UnsupportedSide(side) => {
+        write!(
+          f,
+          "No labels for a {side}x{side} square, expected 5x5 or 6x6"
+        )
+      }
+    }
+  }
+}
+
+impl AdfgxKey {
+  /// Parses "square,transposition": the keyword filling the square and the
+  /// keyword ordering the columns.
+  pub fn try_new(key: &str, context: &Adfgx) -> Result<Self, ParseError> {
+    let (square, transposition) =
+      key.split_once(',').ok_or(ParseError::InvalidFormat)?;
+    let square = context.square(square)?;
+    let transposition =
+      ColumnarKey::try_new(transposition.to_string(), &context.columnar)
+        .map_err(ParseError::Transposition)?;
+
+    Ok(AdfgxKey::new(square, transposition))
+  }
+
+  pub fn new(square: PolybiusSquare, transposition: ColumnarKey) -> Self {
+    AdfgxKey {
+      square,
+      transposition,
+    }
+  }
+
+  pub fn square(&self) -> &PolybiusSquare {
+    &self.square
+  }
+
+  pub fn transposition(&self) -> &ColumnarKey {
+    &self.transposition
+  }
+}
+
+impl TryFrom<(&str, &Adfgx)> for AdfgxKey {
+  type Error = ParseError;
+
+  fn try_from((key, context): (&str, &Adfgx)) -> Result<Self, Self::Error> {
+    Self::try_new(key, context)
+  }
+}
+
+impl fmt::Display for AdfgxKey {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(f, "{},{}", self.square, self.transposition)
+  }
+}
+
+impl Adfgx {
+  pub fn new(alphabet: Alphabet, merge: Option<(char, char)>) -> Self {
+    Adfgx {
+      columnar: Columnar::new(alphabet.clone(), true, None),
+      alphabet,
+      merge,
+    }
+  }
+
+  /// The square filled with `keyword` and the rest of the alphabet, which
+  /// must fit a square with labels.
+  pub fn square(&self, keyword: &str) -> Result<PolybiusSquare, ParseError> {
+    let square = PolybiusSquare::new(keyword, &self.alphabet, self.merge)
+      .map_err(ParseError::Square)?;
+    label_names(square.side())?;
+
+    Ok(square)
+  }
+
+  /// Row and column indices of the letters of `ciphertext`, with the
+  /// transposition undone.
+  fn coordinates(
+    &self,
+    ciphertext: &str,
+    side: usize,
+    transposition: &ColumnarKey,
+  ) -> Vec<(usize, usize)> {
+    let Ok(names) = label_names(side) else {
+      return Vec::new();
+    };
+    let labels: String =
+      ciphertext.chars().filter(|c| names.contains(c)).collect();
+    let indices: Vec<usize> = self
+      .columnar
+      .decipher(&labels, transposition)
+      .chars()
+      .filter_map(|c| names.iter().position(|&l| l == c))
+      .collect();
+
+    indices
+      .chunks_exact(2)
+      .map(|pair| (pair[0], pair[1]))
+      .collect()
+  }
+}
+
+fn label_names(side: usize) -> Result<&'static [char], ParseError> {
+  match side {
+    5 => Ok(&ADFGX),
+    6 => Ok(&ADFGVX),
+    _ => Err(ParseError::UnsupportedSide(side)),
+  }
+}
+
+impl Encipher for Adfgx {
+  type Key = AdfgxKey;
+
+  fn encipher(&self, plaintext: &str, key: &Self::Key) -> String {
+    let Ok(names) = label_names(key.square.side()) else {
+      return String::new();
+    };
+    let labels: String = plaintext
+      .chars()
+      .filter_map(|c| key.square.position(c))
+      .flat_map(|(row, column)| [names[row], names[column]])
+      .collect();
+
+    self.columnar.encipher(&labels, &key.transposition)
+  }
+}
+
+impl Decipher for Adfgx {
+  type Key = AdfgxKey;
+
+  fn decipher(&self, ciphertext: &str, key: &Self::Key) -> String {
+    self
+      .coordinates(ciphertext, key.square.side(), &key.transposition)
+      .into_iter()
+      .filter_map(|(row, column)| key.square.get(row, column))
+      .collect()
+  }
+}
+
+#[cfg(test)]
+mod tests;
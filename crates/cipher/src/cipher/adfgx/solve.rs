@@ -0,0 +1,175 @@
+use super::{Adfgx, AdfgxKey, ParseError};
+use crate::cipher::{
+  Decipher, columnar::ColumnarKey, substitution::Substitution,
+};
+use crate::language::{GetConfidence, Language};
+use crate::periodicity;
+use crate::polybius::PolybiusSquare;
+use crate::search::{self, AnnealOptions};
+use rand::{Rng, RngExt};
+
+impl Adfgx {
+  pub fn random_transposition<R: Rng>(
+    &self,
+    width: usize,
+    rng: &mut R,
+  ) -> ColumnarKey {
+    self.columnar.random_key(width, rng)
+  }
+
+  /// Index of coincidence of the label pairs once `transposition` is
+  /// undone. Only the right transposition pairs every row label with its
+  /// column label, and any other pairing flattens the distribution.
+  pub fn digraph_index_of_coincidence(
+    &self,
+    ciphertext: &str,
+    transposition: &ColumnarKey,
+  ) -> f64 {
+    let Ok(square) = self.square("") else {
+      return 0.0;
+    };
+    let side = square.side();
+    let cells = self
+      .coordinates(ciphertext, side, transposition)
+      .into_iter()
+      .map(|(row, column)| row * side + column);
+
+    periodicity::index_of_coincidence(cells, side * side)
+  }
+
+  /// First stage of the attack: improves the transposition `key` by
+  /// swapping and moving columns, keeping changes that raise the index of
+  /// coincidence of the label pairs. The square plays no part, so this works
+  /// before anything is known about the substitution. Returns the key with
+  /// its index of coincidence.
+  pub fn transposition_hill_climb<R: Rng>(
+    &self,
+    ciphertext: &str,
+    key: ColumnarKey,
+    options: &AnnealOptions,
+    rng: &mut R,
+  ) -> (ColumnarKey, f64) {
+    if key.len() < 2 {
+      let ioc = self.digraph_index_of_coincidence(ciphertext, &key);
+      return (key, ioc);
+    }
+
+    let (order, score) = search::anneal(
+      self.columnar.column_order(&key),
+      options,
+      rng,
+      |order, rng| {
+        let mut order = order.clone();
+        let i = rng.random_range(0..order.len());
+        let j = rng.random_range(0..order.len());
+        if rng.random_bool(0.5) {
+          order.swap(i, j);
+        } else {
+          let column = order.remove(i);
+          order.insert(j, column);
+        }
+        order
+      },
+      |order| {
+        let key = self.columnar.key_from_order(order);
+        -self.digraph_index_of_coincidence(ciphertext, &key)
+      },
+    );
+
+    (self.columnar.key_from_order(&order), -score)
+  }
+
+  /// Pairs the cells of the square with letters of the same frequency rank
+  /// in `language`, once `transposition` is undone.
+  pub fn frequency_key(
+    &self,
+    ciphertext: &str,
+    transposition: ColumnarKey,
+    language: &Language,
+  ) -> Result<AdfgxKey, ParseError> {
+    // Deciphering with an unkeyed square names every cell after a letter,
+    // which turns the rest into a simple substitution.
+    let plain = AdfgxKey::new(self.square("")?, transposition);
+    let text = self.decipher(ciphertext, &plain);
+    let substitution =
+      Substitution::new(plain.square.letters().iter().copied().collect());
+    let key = substitution.frequency_key(&text, language);
+    let square = PolybiusSquare::from_letters(
+      substitution.key_string(&key).chars().collect(),
+      self.merge,
+    )
+    .map_err(ParseError::Square)?;
+
+    Ok(AdfgxKey::new(square, plain.transposition))
+  }
+
+  /// Transpositions that pair the labels the same way as `key`, found by
+  /// reordering its columns two at a time. They all score the same in the
+  /// first stage, and differ only in the order of the plaintext letters
+  /// within a row, so the second stage tries each of them. Odd widths pair
+  /// labels across rows, leaving only `key` itself.
+  pub fn equivalent_transpositions(
+    &self,
+    key: &ColumnarKey,
+  ) -> Vec<ColumnarKey> {
+    let width = key.len();
+    if width < 4 || !width.is_multiple_of(2) {
+      return vec![key.clone()];
+    }
+
+    let order = self.columnar.column_order(key);
+    self
+      .columnar
+      .keys_of_width(width / 2)
+      .map(|pairs| {
+        let pairs = self.columnar.column_order(&pairs);
+        let order: Vec<_> = order
+          .iter()
+          .map(|&column| 2 * pairs[column / 2] + column % 2)
+          .collect();
+        self.columnar.key_from_order(&order)
+      })
+      .collect()
+  }
+
+  /// Second stage of the attack: improves the square of `key` by swapping
+  /// letters, keeping changes that lower the confidence score of the
+  /// deciphered text.
+  pub fn square_hill_climb<R: Rng>(
+    &self,
+    ciphertext: &str,
+    key: AdfgxKey,
+    get_confidence: &GetConfidence,
+    options: &AnnealOptions,
+    rng: &mut R,
+  ) -> (AdfgxKey, f64) {
+    let merge = key.square.merge();
+    let (letters, confidence) = search::anneal(
+      key.square.letters().to_vec(),
+      options,
+      rng,
+      |letters, rng| {
+        let mut letters = letters.clone();
+        let i = rng.random_range(0..letters.len());
+        let j = rng.random_range(0..letters.len());
+        letters.swap(i, j);
+        letters
+      },
+      |letters| match PolybiusSquare::from_letters(letters.clone(), merge) {
+        Ok(square) => {
+          let key = AdfgxKey::new(square, key.transposition.clone());
+          get_confidence.run(&self.decipher(ciphertext, &key))
+        }
+        Err(_) => f64::MAX,
+      },
+    );
+
+    match PolybiusSquare::from_letters(letters, merge) {
+      Ok(square) => (AdfgxKey::new(square, key.transposition), confidence),
+      Err(_) => {
+        let confidence = get_confidence.run(&self.decipher(ciphertext, &key));
+        (key, confidence)
+      }
+    }
+  }
+}
@@ -0,0 +1,140 @@
+use super::*;
+use crate::alphabet::Alphabet;
+use crate::cipher_test;
+use crate::tests::*;
+use once_cell::sync::Lazy;
+
+pub static CONTEXT: Lazy<Adfgx> =
+  Lazy::new(|| Adfgx::new(Alphabet::latin(), Some(('j', 'i'))));
+pub static CONTEXT_ADFGVX: Lazy<Adfgx> = Lazy::new(|| {
+  Adfgx::new(Alphabet::latin().iter().chain('0'..='9').collect(), None)
+});
+
+pub static KEY_A: Lazy<AdfgxKey> = Lazy::new(|| {
+  AdfgxKey::try_new("btalpdhozkqfvsngicuxmrewy,cargo", &CONTEXT).unwrap()
+});
+pub static KEY_B: Lazy<AdfgxKey> = Lazy::new(|| {
+  AdfgxKey::try_new(
+    "na1c3h8tb2ome5wrpd4f6g7i9j0klqsuvxyz,privacy",
+    &CONTEXT_ADFGVX,
+  )
+  .unwrap()
+});
+
+// The tale without the letters the square leaves out.
+static TALE: Lazy<String> = Lazy::new(|| {
+  A_TALE_OF_TWO_CITIES
+    .chars()
+    .filter(|c| c.is_alphabetic())
+    .map(|c| if c == 'j' { 'i' } else { c })
+    .take(600)
+    .collect()
+});
+
+static FOX: Lazy<String> = Lazy::new(|| QUICKBROWNFOX.trim().to_string());
+
+static ATTACK_AT_ONCE: Lazy<String> = Lazy::new(|| "attackatonce".to_string());
+
+#[test]
+fn key_try_new() {
+  assert!(matches!(
+    AdfgxKey::try_new("keyword", &CONTEXT),
+    Err(ParseError::InvalidFormat),
+  ));
+  assert!(matches!(
+    AdfgxKey::try_new("key1,word", &CONTEXT),
+    Err(ParseError::Square(polybius::ParseError::InvalidChar('1'))),
+  ));
+  assert!(matches!(
+    AdfgxKey::try_new(",", &Adfgx::new(Alphabet::greek(), None)),
+    Err(ParseError::Square(polybius::ParseError::NotSquare(24))),
+  ));
+  assert!(matches!(
+    AdfgxKey::try_new(
+      "bad,face",
+      &Adfgx::new(Alphabet::latin().iter().take(16).collect(), None)
+    ),
+    Err(ParseError::UnsupportedSide(4)),
+  ));
+}
+
+// The square and keys of both tests are Wikipedia's examples.
+#[test]
+fn example_adfgx() {
+  assert_eq!(
+    CONTEXT.encipher("attack at once", &KEY_A),
+    "faxdfadddgdgfffafaxafafx"
+  );
+}
+
+#[test]
+fn example_adfgvx() {
+  assert_eq!(
+    CONTEXT_ADFGVX.encipher("attack at 1200am", &KEY_B),
+    "dgdddagddgafaddfdadvdvfaadvx"
+  );
+}
+
+#[test]
+fn solve() {
+  use crate::language::GetConfidence;
+  use crate::search::AnnealOptions;
+  use rand::{SeedableRng, rngs::StdRng};
+  use std::sync::Arc;
+
+  let key =
+    AdfgxKey::try_new("btalpdhozkqfvsngicuxmrewy,zebras", &CONTEXT).unwrap();
+  let ciphertext = CONTEXT.encipher(&TALE, &key);
+  let table = ENGLISH.log_ngram_table(3).unwrap();
+  let get_confidence =
+    GetConfidence::new(Arc::new(move |text: &str| table.text_confidence(text)));
+  let mut rng = StdRng::seed_from_u64(0);
+
+  let options = AnnealOptions {
+    iterations: 1000,
+    temperature: 0.0,
+  };
+  let (transposition, ioc) = (0..10)
+    .map(|_| {
+      let transposition = CONTEXT.random_transposition(6, &mut rng);
+      CONTEXT.transposition_hill_climb(
+        &ciphertext,
+        transposition,
+        &options,
+        &mut rng,
+      )
+    })
+    .max_by(|a, b| a.1.total_cmp(&b.1))
+    .unwrap();
+  assert_eq!(
+    ioc,
+    CONTEXT.digraph_index_of_coincidence(&ciphertext, key.transposition())
+  );
+
+  let options = AnnealOptions {
+    iterations: 5000,
+    temperature: 0.1,
+  };
+  let (key, _) = CONTEXT
+    .equivalent_transpositions(&transposition)
+    .into_iter()
+    .map(|transposition| {
+      let key = CONTEXT
+        .frequency_key(&ciphertext, transposition, &ENGLISH)
+        .unwrap();
+      CONTEXT.square_hill_climb(
+        &ciphertext,
+        key,
+        &get_confidence,
+        &options,
+        &mut rng,
+      )
+    })
+    .min_by(|a, b| a.1.total_cmp(&b.1))
+    .unwrap();
+
+  assert_eq!(CONTEXT.decipher(&ciphertext, &key), *TALE);
+}
+
+cipher_test!(CONTEXT, *ATTACK_AT_ONCE, KEY_A, attack_at_once);
+cipher_test!(CONTEXT_ADFGVX, *FOX, KEY_B, quick_brown_fox);
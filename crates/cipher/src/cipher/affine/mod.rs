@@ -0,0 +1,147 @@
+use crate::{
+  alphabet::Alphabet,
+  cipher::{
+    Decipher, Encipher, KeysIterator,
+    modular::{gcd, modular_inverse},
+  },
+};
+use cipher_derive::{IntoDecipherKey, IntoEncipherKey};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// Maps the letter at index `x` to the letter at index `a * x + b`.
+#[derive(
+  Debug, Clone, Serialize, Deserialize, IntoDecipherKey, IntoEncipherKey,
+)]
+pub struct AffineKey {
+  a: usize,
+  b: usize,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Affine {
+  alphabet: Alphabet,
+}
+
+#[derive(Debug)]
+pub enum ParseError {
+  InvalidFormat(String),
+  NotCoprime(usize, usize),
+}
+
+impl fmt::Display for ParseError {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    match self {
+      ParseError::InvalidFormat(key) => {
+        write!(f, "Invalid key: '{key}', expected 'a,b'")
+      }
+      ParseError::NotCoprime(a, len) => write!(
+        f,
+        "Multiplier {a} is not coprime with the alphabet length {len}"
+      ),
+    }
+  }
+}
+
+impl AffineKey {
+  /// Takes `a` and `b` modulo the alphabet length, so any multiplier
+  /// coprime with it is accepted.
+  pub fn try_new(
+    a: usize,
+    b: usize,
+    context: &Affine,
+  ) -> Result<Self, ParseError> {
+    let len = context.alphabet.len();
+    if gcd(a % len, len) != 1 {
+      return Err(ParseError::NotCoprime(a, len));
+    }
+
+    Ok(Self::new(a % len, b % len))
+  }
+
+  pub fn new(a: usize, b: usize) -> Self {
+    AffineKey { a, b }
+  }
+}
+
+impl TryFrom<(&str, &Affine)> for AffineKey {
+  type Error = ParseError;
+
+  fn try_from((key, context): (&str, &Affine)) -> Result<Self, Self::Error> {
+    let invalid = || ParseError::InvalidFormat(key.to_string());
+    let (a, b) = key.split_once(',').ok_or_else(invalid)?;
+    let a = a.trim().parse().map_err(|_| invalid())?;
+    let b = b.trim().parse().map_err(|_| invalid())?;
+
+    Self::try_new(a, b, context)
+  }
+}
+
+impl fmt::Display for AffineKey {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(f, "{},{}", self.a, self.b)
+  }
+}
+
+impl Affine {
+  pub fn new(alphabet: Alphabet) -> Self {
+    Affine { alphabet }
+  }
+
+  fn map(&self, c: char, f: impl Fn(usize) -> usize) -> char {
+    self
+      .alphabet
+      .get_index(c)
+      .and_then(|x| self.alphabet.get(f(x) % self.alphabet.len()))
+      .unwrap_or(c)
+  }
+}
+
+impl Encipher for Affine {
+  type Key = AffineKey;
+
+  fn encipher(&self, plaintext: &str, key: &Self::Key) -> String {
+    let len = self.alphabet.len();
+
+    plaintext
+      .chars()
+      .map(|c| self.map(c, |x| key.a % len * x + key.b % len))
+      .collect()
+  }
+}
+
+impl Decipher for Affine {
+  type Key = AffineKey;
+
+  fn decipher(&self, ciphertext: &str, key: &Self::Key) -> String {
+    let len = self.alphabet.len();
+    // Keys built with `AffineKey::new` are not validated; leave the text
+    // unchanged rather than guess an inverse that does not exist.
+    let Some(a_inverse) = modular_inverse(key.a % len, len) else {
+      return ciphertext.to_string();
+    };
+
+    ciphertext
+      .chars()
+      .map(|c| self.map(c, |y| a_inverse * (y + len - key.b % len)))
+      .collect()
+  }
+}
+
+impl KeysIterator for Affine {
+  type KeysIter =
+    <std::vec::Vec<AffineKey> as std::iter::IntoIterator>::IntoIter;
+
+  fn keys_iter(&self) -> Self::KeysIter {
+    let len = self.alphabet.len();
+    let keys: Vec<AffineKey> = (1..len)
+      .filter(|&a| gcd(a, len) == 1)
+      .flat_map(|a| (0..len).map(move |b| AffineKey::new(a, b)))
+      .collect();
+
+    keys.into_iter()
+  }
+}
+
+#[cfg(test)]
+mod tests;
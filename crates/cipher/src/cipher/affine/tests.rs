@@ -0,0 +1,51 @@
+use super::*;
+use crate::alphabet::Alphabet;
+use crate::cipher_test;
+use crate::tests::*;
+use once_cell::sync::Lazy;
+
+pub static CONTEXT: Lazy<Affine> = Lazy::new(|| Affine::new(Alphabet::latin()));
+
+pub static KEY_1_0: Lazy<AffineKey> = Lazy::new(|| AffineKey::new(1, 0));
+pub static KEY_5_8: Lazy<AffineKey> = Lazy::new(|| AffineKey::new(5, 8));
+
+#[test]
+fn key_try_new() {
+  let valid = [1, 3, 5, 7, 9, 11, 15, 17, 19, 21, 23, 25];
+  let invalid = [0, 2, 4, 6, 8, 10, 12, 13, 14, 16, 18, 20, 22, 24, 26];
+
+  valid
+    .iter()
+    .for_each(|&a| assert!(AffineKey::try_new(a, 0, &CONTEXT).is_ok()));
+  invalid
+    .iter()
+    .for_each(|&a| assert!(AffineKey::try_new(a, 0, &CONTEXT).is_err()));
+}
+
+#[test]
+fn key_try_new_reduces() {
+  let key = AffineKey::try_new(27, 34, &CONTEXT).unwrap();
+  assert_eq!(key.to_string(), "1,8");
+
+  let key = AffineKey::try_from(("9999999999999999999,1", &*CONTEXT)).unwrap();
+  assert_eq!(key.to_string(), "9,1");
+  assert_eq!(CONTEXT.encipher("hello", &key), "mlwwx");
+}
+
+#[test]
+fn key_try_from() {
+  assert!(AffineKey::try_from(("5,8", &*CONTEXT)).is_ok());
+  assert!(AffineKey::try_from(("13,8", &*CONTEXT)).is_err());
+  assert!(AffineKey::try_from(("5", &*CONTEXT)).is_err());
+  assert!(AffineKey::try_from(("a,b", &*CONTEXT)).is_err());
+}
+
+#[test]
+fn keys_iter() {
+  assert_eq!(CONTEXT.keys_iter().count(), 12 * 26);
+}
+
+cipher_test!(CONTEXT, *QUICK_BROWN_FOX, KEY_1_0, latin_1_0_spaces);
+cipher_test!(CONTEXT, *QUICKBROWNFOX, KEY_1_0, latin_1_0_no_spaces);
+cipher_test!(CONTEXT, *QUICK_BROWN_FOX, KEY_5_8, latin_5_8_spaces);
+cipher_test!(CONTEXT, *QUICKBROWNFOX, KEY_5_8, latin_5_8_no_spaces);
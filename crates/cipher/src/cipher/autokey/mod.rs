@@ -1,3 +1,5 @@
+mod solve;
+
 use crate::{
   alphabet::Alphabet,
   cipher::{Decipher, Encipher},
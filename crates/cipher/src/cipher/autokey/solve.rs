@@ -0,0 +1,87 @@
+use super::{Autokey, AutokeyKey};
+use crate::cipher::Decipher;
+use crate::language::{GetConfidence, Language};
+use crate::search::{self, AnnealOptions};
+use rand::{Rng, RngExt};
+
+impl Autokey {
+  /// Recovers a primer of `primer_length` letters one position at a time.
+  /// Every primer letter deciphers its own chain of ciphertext letters
+  /// (positions `i`, `i + primer_length`, ...), since each plaintext letter
+  /// is the key for the one `primer_length` places later, so each position
+  /// is solved independently against the unigram statistics of `language`.
+  pub fn frequency_key(
+    &self,
+    ciphertext: &str,
+    primer_length: usize,
+    language: &Language,
+  ) -> AutokeyKey {
+    let letters: Vec<char> = ciphertext
+      .chars()
+      .filter(|c| !(c.is_whitespace() && self.skip_whitespace))
+      .collect();
+
+    let key = (0..primer_length)
+      .filter_map(|offset| {
+        self
+          .alphabet
+          .iter()
+          .map(|k| {
+            let chain: String = letters
+              .iter()
+              .skip(offset)
+              .step_by(primer_length)
+              .scan(k, |k, &c| {
+                *k = self.alphabet.sub(c, *k);
+                Some(*k)
+              })
+              .filter(|&p| self.alphabet.contains(p))
+              .collect();
+            (k, language.text_confidence_chi2_unigram(&chain))
+          })
+          .min_by(|a, b| {
+            a.1.partial_cmp(&b.1).unwrap_or(std::cmp::Ordering::Equal)
+          })
+          .map(|(k, _)| k)
+      })
+      .collect();
+
+    AutokeyKey::new(key)
+  }
+
+  /// Improves `key` by changing one primer letter at a time, keeping changes
+  /// that lower the confidence score of the deciphered text.
+  pub fn hill_climb<R: Rng>(
+    &self,
+    ciphertext: &str,
+    key: AutokeyKey,
+    get_confidence: &GetConfidence,
+    options: &AnnealOptions,
+    rng: &mut R,
+  ) -> (AutokeyKey, f64) {
+    if key.is_empty() {
+      let confidence = get_confidence.run(ciphertext);
+      return (key, confidence);
+    }
+
+    let letters: Vec<_> = key.0.chars().collect();
+    let (letters, confidence) = search::anneal(
+      letters,
+      options,
+      rng,
+      |letters, rng| {
+        let mut letters = letters.clone();
+        let i = rng.random_range(0..letters.len());
+        let k = rng.random_range(0..self.alphabet.len());
+        letters[i] = self.alphabet.get(k).unwrap_or(letters[i]);
+        letters
+      },
+      |letters| {
+        let key = AutokeyKey::new(letters.iter().collect());
+        get_confidence.run(&self.decipher(ciphertext, &key))
+      },
+    );
+
+    (AutokeyKey::new(letters.into_iter().collect()), confidence)
+  }
+}
@@ -38,3 +38,13 @@ cipher_test!(
   KEY_A,
   skip_whitespace
 );
+
+#[test]
+fn frequency_key() {
+  let key = AutokeyKey::new("queen".to_string());
+  let ciphertext =
+    CONTEXT_SKIP_WHITESPACE.encipher(&A_TALE_OF_TWO_CITIES, &key);
+
+  let solved = CONTEXT_SKIP_WHITESPACE.frequency_key(&ciphertext, 5, &ENGLISH);
+  assert_eq!(solved.to_string(), "queen");
+}
@@ -0,0 +1,131 @@
+use crate::{
+  alphabet::Alphabet,
+  cipher::{Decipher, Encipher},
+};
+use cipher_derive::{IntoDecipherKey, IntoEncipherKey};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use stutter_zip::StutterZipIterator;
+
+#[derive(
+  Debug, Clone, Serialize, Deserialize, IntoDecipherKey, IntoEncipherKey,
+)]
+pub struct BeaufortKey(String);
+
+/// Beaufort enciphers as `key - plaintext`, which makes it its own inverse.
+/// The variant enciphers as `plaintext - key`, Vigenère run backwards.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Beaufort {
+  alphabet: Alphabet,
+  skip_whitespace: bool,
+  variant: bool,
+}
+
+#[derive(Debug)]
+pub enum ParseError {
+  InvalidChar(char),
+}
+
+impl fmt::Display for ParseError {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    match self {
+      ParseError::InvalidChar(c) => write!(f, "Invalid character: '{c}'"),
+    }
+  }
+}
+
+impl BeaufortKey {
+  pub fn len(&self) -> usize {
+    self.0.len()
+  }
+
+  pub fn is_empty(&self) -> bool {
+    self.0.is_empty()
+  }
+
+  pub fn try_new(key: String, context: &Beaufort) -> Result<Self, ParseError> {
+    if let Some(k) = key.chars().find(|&k| !context.alphabet.contains(k)) {
+      return Err(ParseError::InvalidChar(k));
+    }
+
+    Ok(BeaufortKey::new(key))
+  }
+
+  pub fn new(key: String) -> Self {
+    BeaufortKey(key)
+  }
+}
+
+impl TryFrom<(&str, &Beaufort)> for BeaufortKey {
+  type Error = ParseError;
+
+  fn try_from((key, context): (&str, &Beaufort)) -> Result<Self, Self::Error> {
+    Self::try_new(key.to_string(), context)
+  }
+}
+
+impl std::fmt::Display for BeaufortKey {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    write!(f, "{}", self.0)
+  }
+}
+
+impl Beaufort {
+  pub fn new(alphabet: Alphabet, skip_whitespace: bool) -> Self {
+    Beaufort {
+      alphabet,
+      skip_whitespace,
+      variant: false,
+    }
+  }
+
+  pub fn variant(alphabet: Alphabet, skip_whitespace: bool) -> Self {
+    Beaufort {
+      alphabet,
+      skip_whitespace,
+      variant: true,
+    }
+  }
+
+  fn apply(&self, text: &str, key: &BeaufortKey, encipher: bool) -> String {
+    if key.is_empty() {
+      return text.to_string();
+    }
+
+    text
+      .chars()
+      .stutter_zip(key.0.chars().cycle(), |c| {
+        c.is_whitespace() && self.skip_whitespace
+      })
+      .map(|(c, k)| match k {
+        Some(k) if self.alphabet.contains(c) => {
+          match (self.variant, encipher) {
+            (false, _) => self.alphabet.sub(k, c),
+            (true, true) => self.alphabet.sub(c, k),
+            (true, false) => self.alphabet.add(c, k),
+          }
+        }
+        _ => c,
+      })
+      .collect()
+  }
+}
+
+impl Encipher for Beaufort {
+  type Key = BeaufortKey;
+
+  fn encipher(&self, plaintext: &str, key: &Self::Key) -> String {
+    self.apply(plaintext, key, true)
+  }
+}
+
+impl Decipher for Beaufort {
+  type Key = BeaufortKey;
+
+  fn decipher(&self, ciphertext: &str, key: &Self::Key) -> String {
+    self.apply(ciphertext, key, false)
+  }
+}
+
+#[cfg(test)]
+mod tests;
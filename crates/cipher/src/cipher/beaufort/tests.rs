@@ -0,0 +1,67 @@
+use super::*;
+use crate::alphabet::Alphabet;
+use crate::cipher_test;
+use crate::tests::*;
+use once_cell::sync::Lazy;
+
+pub static CONTEXT_INCLUDE_WHITESPACE: Lazy<Beaufort> =
+  Lazy::new(|| Beaufort::new(Alphabet::latin(), false));
+pub static CONTEXT_SKIP_WHITESPACE: Lazy<Beaufort> =
+  Lazy::new(|| Beaufort::new(Alphabet::latin(), true));
+pub static VARIANT_INCLUDE_WHITESPACE: Lazy<Beaufort> =
+  Lazy::new(|| Beaufort::variant(Alphabet::latin(), false));
+pub static VARIANT_SKIP_WHITESPACE: Lazy<Beaufort> =
+  Lazy::new(|| Beaufort::variant(Alphabet::latin(), true));
+
+pub static KEY_A: Lazy<BeaufortKey> =
+  Lazy::new(|| BeaufortKey::new("key".to_string()));
+
+#[test]
+fn key_try_new() {
+  assert!(
+    BeaufortKey::try_new("hello".to_string(), &CONTEXT_INCLUDE_WHITESPACE)
+      .is_ok()
+  );
+  assert!(matches!(
+    BeaufortKey::try_new("gr8ness".to_string(), &CONTEXT_INCLUDE_WHITESPACE),
+    Err(ParseError::InvalidChar('8')),
+  ));
+}
+
+#[test]
+fn beaufort_is_reciprocal() {
+  let ciphertext = CONTEXT_SKIP_WHITESPACE.encipher(&QUICK_BROWN_FOX, &KEY_A);
+
+  assert_eq!(
+    CONTEXT_SKIP_WHITESPACE.encipher(&ciphertext, &KEY_A),
+    *QUICK_BROWN_FOX
+  );
+}
+
+cipher_test!(
+  CONTEXT_INCLUDE_WHITESPACE,
+  *QUICK_BROWN_FOX,
+  KEY_A,
+  include_whitespace
+);
+
+cipher_test!(
+  CONTEXT_SKIP_WHITESPACE,
+  *QUICK_BROWN_FOX,
+  KEY_A,
+  skip_whitespace
+);
+
+cipher_test!(
+  VARIANT_INCLUDE_WHITESPACE,
+  *QUICK_BROWN_FOX,
+  KEY_A,
+  variant_include_whitespace
+);
+
+cipher_test!(
+  VARIANT_SKIP_WHITESPACE,
+  *QUICK_BROWN_FOX,
+  KEY_A,
+  variant_skip_whitespace
+);
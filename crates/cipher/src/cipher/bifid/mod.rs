@@ -0,0 +1,135 @@
+use crate::{
+  alphabet::Alphabet,
+  cipher::{Decipher, Encipher, fractionation},
+  periodicity::{self, PeriodCandidate},
+  polybius::{ParseError, PolybiusSquare},
+};
+use cipher_derive::{IntoDecipherKey, IntoEncipherKey};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+#[derive(
+  Debug, Clone, Serialize, Deserialize, IntoDecipherKey, IntoEncipherKey,
+)]
+pub struct BifidKey(PolybiusSquare);
+
+/// Splits every letter into its row and column in a keyed square, writes
+/// the rows and then the columns of each block of `period` letters, and
+/// reads the coordinates back in pairs. A period of zero uses the whole
+/// text as one block. Letters not in the square are dropped.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Bifid {
+  alphabet: Alphabet,
+  merge: Option<(char, char)>,
+  period: usize,
+}
+
+impl BifidKey {
+  pub fn try_new(keyword: &str, context: &Bifid) -> Result<Self, ParseError> {
+    PolybiusSquare::new(keyword, &context.alphabet, context.merge)
+      .map(BifidKey::new)
+  }
+
+  pub fn new(square: PolybiusSquare) -> Self {
+    BifidKey(square)
+  }
+}
+
+impl TryFrom<(&str, &Bifid)> for BifidKey {
+  type Error = ParseError;
+
+  fn try_from((key, context): (&str, &Bifid)) -> Result<Self, Self::Error> {
+    Self::try_new(key, context)
+  }
+}
+
+impl fmt::Display for BifidKey {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(f, "{}", self.0)
+  }
+}
+
+impl Bifid {
+  pub fn new(
+    alphabet: Alphabet,
+    merge: Option<(char, char)>,
+    period: usize,
+  ) -> Self {
+    Bifid {
+      alphabet,
+      merge,
+      period,
+    }
+  }
+
+  /// Ranks the likely periods of `ciphertext`, best first.
+  pub fn periods(
+    &self,
+    ciphertext: &str,
+    max_period: usize,
+  ) -> Vec<PeriodCandidate> {
+    let Ok(square) = PolybiusSquare::new("", &self.alphabet, self.merge) else {
+      return Vec::new();
+    };
+    let letters: Vec<usize> = self
+      .coordinates(ciphertext, &square)
+      .into_iter()
+      .map(|[row, column]| row * square.side() + column)
+      .collect();
+
+    periodicity::rank_fractionation_periods(
+      &letters,
+      square.letters().len(),
+      2,
+      max_period,
+    )
+  }
+
+  fn coordinates(
+    &self,
+    text: &str,
+    square: &PolybiusSquare,
+  ) -> Vec<[usize; 2]> {
+    text
+      .chars()
+      .filter_map(|c| square.position(c))
+      .map(|(row, column)| [row, column])
+      .collect()
+  }
+
+  fn letters(
+    &self,
+    coordinates: &[[usize; 2]],
+    square: &PolybiusSquare,
+  ) -> String {
+    coordinates
+      .iter()
+      .filter_map(|&[row, column]| square.get(row, column))
+      .collect()
+  }
+}
+
+impl Encipher for Bifid {
+  type Key = BifidKey;
+
+  fn encipher(&self, plaintext: &str, key: &Self::Key) -> String {
+    let coordinates = self.coordinates(plaintext, &key.0);
+    let coordinates = fractionation::fractionate(&coordinates, self.period);
+
+    self.letters(&coordinates, &key.0)
+  }
+}
+
+impl Decipher for Bifid {
+  type Key = BifidKey;
+
+  fn decipher(&self, ciphertext: &str, key: &Self::Key) -> String {
+    let coordinates = self.coordinates(ciphertext, &key.0);
+    let coordinates = fractionation::unfractionate(&coordinates, self.period);
+
+    self.letters(&coordinates, &key.0)
+  }
+}
+
+#[cfg(test)]
+mod tests;
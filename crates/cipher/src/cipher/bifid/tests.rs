@@ -0,0 +1,52 @@
+use super::*;
+use crate::alphabet::Alphabet;
+use crate::cipher_test;
+use crate::tests::*;
+use once_cell::sync::Lazy;
+
+pub static CONTEXT: Lazy<Bifid> =
+  Lazy::new(|| Bifid::new(Alphabet::latin(), Some(('j', 'i')), 0));
+pub static CONTEXT_PERIOD_5: Lazy<Bifid> =
+  Lazy::new(|| Bifid::new(Alphabet::latin(), Some(('j', 'i')), 5));
+
+pub static KEY_A: Lazy<BifidKey> = Lazy::new(|| {
+  BifidKey::try_new("bgwkzqpndsioaxefclumthyvr", &CONTEXT).unwrap()
+});
+
+static FLEE_AT_ONCE: Lazy<String> = Lazy::new(|| "fleeatonce".to_string());
+
+// The tale without the letters the square leaves out.
+static TALE: Lazy<String> = Lazy::new(|| {
+  A_TALE_OF_TWO_CITIES
+    .chars()
+    .filter(|c| c.is_alphabetic())
+    .map(|c| if c == 'j' { 'i' } else { c })
+    .collect()
+});
+
+#[test]
+fn key_try_new() {
+  assert!(matches!(
+    BifidKey::try_new("gr8", &CONTEXT),
+    Err(ParseError::InvalidChar('8')),
+  ));
+}
+
+#[test]
+fn wikipedia_example() {
+  assert_eq!(CONTEXT.encipher("flee at once", &KEY_A), "uaeolwrins");
+}
+
+#[test]
+fn periods() {
+  for period in [5, 7, 8] {
+    let context = Bifid::new(Alphabet::latin(), Some(('j', 'i')), period);
+    let ciphertext = context.encipher(&TALE, &KEY_A);
+
+    assert_eq!(context.decipher(&ciphertext, &KEY_A), *TALE);
+    assert_eq!(context.periods(&ciphertext, 12)[0].period, period);
+  }
+}
+
+cipher_test!(CONTEXT, *FLEE_AT_ONCE, KEY_A, whole_text);
+cipher_test!(CONTEXT_PERIOD_5, *FLEE_AT_ONCE, KEY_A, period_5);
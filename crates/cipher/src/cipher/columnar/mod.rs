@@ -0,0 +1,164 @@
+mod solve;
+
+use crate::{
+  alphabet::Alphabet,
+  cipher::{Decipher, Encipher, transposition},
+};
+use cipher_derive::{IntoDecipherKey, IntoEncipherKey};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+pub use solve::Permutations;
+
+/// A keyword with one letter per column. Columns are read in alphabet order
+/// of their letters, left to right among repeated letters.
+#[derive(
+  Debug, Clone, Serialize, Deserialize, IntoDecipherKey, IntoEncipherKey,
+)]
+pub struct ColumnarKey(String);
+
+/// Writes the text into rows as wide as the key and reads it out by
+/// columns. Without `padding` the last row may be incomplete; with it the
+/// last row is filled with the padding letter, which deciphering keeps.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Columnar {
+  alphabet: Alphabet,
+  skip_whitespace: bool,
+  padding: Option<char>,
+}
+
+#[derive(Debug)]
+pub enum ParseError {
+  InvalidChar(char),
+}
+
+impl fmt::Display for ParseError {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    match self {
+      ParseError::InvalidChar(c) => write!(f, "Invalid character: '{c}'"),
+    }
+  }
+}
+
+impl ColumnarKey {
+  pub fn len(&self) -> usize {
+    self.0.chars().count()
+  }
+
+  pub fn is_empty(&self) -> bool {
+    self.0.is_empty()
+  }
+
+  pub fn try_new(key: String, context: &Columnar) -> Result<Self, ParseError> {
+    if let Some(k) = key.chars().find(|&k| !context.alphabet.contains(k)) {
+      return Err(ParseError::InvalidChar(k));
+    }
+
+    Ok(ColumnarKey::new(key))
+  }
+
+  pub fn new(key: String) -> Self {
+    ColumnarKey(key)
+  }
+}
+
+impl TryFrom<(&str, &Columnar)> for ColumnarKey {
+  type Error = ParseError;
+
+  fn try_from((key, context): (&str, &Columnar)) -> Result<Self, Self::Error> {
+    Self::try_new(key.to_string(), context)
+  }
+}
+
+impl std::fmt::Display for ColumnarKey {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    write!(f, "{}", self.0)
+  }
+}
+
+impl Columnar {
+  pub fn new(
+    alphabet: Alphabet,
+    skip_whitespace: bool,
+    padding: Option<char>,
+  ) -> Self {
+    Columnar {
+      alphabet,
+      skip_whitespace,
+      padding,
+    }
+  }
+
+  /// Columns in the order they are read.
+  pub fn column_order(&self, key: &ColumnarKey) -> Vec<usize> {
+    let mut order: Vec<_> = key
+      .0
+      .chars()
+      .enumerate()
+      .map(|(i, k)| (self.alphabet.get_index(k), i))
+      .collect();
+    order.sort();
+
+    order.into_iter().map(|(_, i)| i).collect()
+  }
+
+  /// The key that reads columns in `order`. Needs at least as many letters
+  /// in the alphabet as there are columns.
+  pub fn key_from_order(&self, order: &[usize]) -> ColumnarKey {
+    let mut letters = vec!['?'; order.len()];
+    for (rank, &column) in order.iter().enumerate() {
+      letters[column] = self.alphabet.get(rank).unwrap_or('?');
+    }
+
+    ColumnarKey(letters.into_iter().collect())
+  }
+}
+
+/// Positions of the written text in the order they are read out.
+fn read_positions(len: usize, order: &[usize]) -> Vec<usize> {
+  order
+    .iter()
+    .flat_map(|&column| (column..len).step_by(order.len()))
+    .collect()
+}
+
+impl Encipher for Columnar {
+  type Key = ColumnarKey;
+
+  fn encipher(&self, plaintext: &str, key: &Self::Key) -> String {
+    if key.is_empty() {
+      return plaintext.to_string();
+    }
+
+    let mut letters = transposition::letters(plaintext, self.skip_whitespace);
+    if let Some(padding) = self.padding {
+      while !letters.len().is_multiple_of(key.len()) {
+        letters.push(padding);
+      }
+    }
+
+    let positions = read_positions(letters.len(), &self.column_order(key));
+    let ciphertext = transposition::read(&letters, &positions);
+
+    transposition::reinsert(plaintext, self.skip_whitespace, ciphertext)
+  }
+}
+
+impl Decipher for Columnar {
+  type Key = ColumnarKey;
+
+  fn decipher(&self, ciphertext: &str, key: &Self::Key) -> String {
+    if key.is_empty() {
+      return ciphertext.to_string();
+    }
+
+    let letters = transposition::letters(ciphertext, self.skip_whitespace);
+    let positions = read_positions(letters.len(), &self.column_order(key));
+    let plaintext = transposition::write(&letters, &positions);
+
+    transposition::reinsert(ciphertext, self.skip_whitespace, plaintext)
+  }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,92 @@
+use super::{Columnar, ColumnarKey};
+use crate::cipher::Decipher;
+use crate::language::GetConfidence;
+use crate::search::{self, AnnealOptions};
+use rand::{Rng, RngExt, seq::SliceRandom};
+
+/// Every key of a given width, in lexicographic order of column orders.
+pub struct Permutations<'a> {
+  context: &'a Columnar,
+  order: Option<Vec<usize>>,
+}
+
+impl Iterator for Permutations<'_> {
+  type Item = ColumnarKey;
+
+  fn next(&mut self) -> Option<Self::Item> {
+    let order = self.order.as_mut()?;
+    let key = self.context.key_from_order(order);
+
+    // Advance to the next permutation, or finish after the last one.
+    match (1..order.len()).rev().find(|&i| order[i - 1] < order[i]) {
+      Some(i) => {
+        let j = (i..order.len())
+          .rev()
+          .find(|&j| order[i - 1] < order[j])
+          .unwrap_or(i);
+        order.swap(i - 1, j);
+        order[i..].reverse();
+      }
+      None => self.order = None,
+    }
+
+    Some(key)
+  }
+}
+
+impl Columnar {
+  pub fn keys_of_width(&self, width: usize) -> Permutations<'_> {
+    Permutations {
+      context: self,
+      order: Some((0..width).collect()),
+    }
+  }
+
+  pub fn random_key<R: Rng>(&self, width: usize, rng: &mut R) -> ColumnarKey {
+    let mut order: Vec<_> = (0..width).collect();
+    order.shuffle(rng);
+
+    self.key_from_order(&order)
+  }
+
+  /// Improves `key` by swapping columns and moving single columns to other
+  /// places in the reading order, keeping changes that lower the confidence
+  /// score of the deciphered text.
+  pub fn hill_climb<R: Rng>(
+    &self,
+    ciphertext: &str,
+    key: ColumnarKey,
+    get_confidence: &GetConfidence,
+    options: &AnnealOptions,
+    rng: &mut R,
+  ) -> (ColumnarKey, f64) {
+    if key.len() < 2 {
+      let confidence = get_confidence.run(&self.decipher(ciphertext, &key));
+      return (key, confidence);
+    }
+
+    let (order, confidence) = search::anneal(
+      self.column_order(&key),
+      options,
+      rng,
+      |order, rng| {
+        let mut order = order.clone();
+        let i = rng.random_range(0..order.len());
+        let j = rng.random_range(0..order.len());
+        if rng.random_bool(0.5) {
+          order.swap(i, j);
+        } else {
+          let column = order.remove(i);
+          order.insert(j, column);
+        }
+        order
+      },
+      |order| {
+        let key = self.key_from_order(order);
+        get_confidence.run(&self.decipher(ciphertext, &key))
+      },
+    );
+
+    (self.key_from_order(&order), confidence)
+  }
+}
@@ -0,0 +1,132 @@
+use super::*;
+use crate::alphabet::Alphabet;
+use crate::cipher_test;
+use crate::language::GetConfidence;
+use crate::tests::*;
+use once_cell::sync::Lazy;
+use std::sync::Arc;
+
+pub static CONTEXT_INCLUDE_WHITESPACE: Lazy<Columnar> =
+  Lazy::new(|| Columnar::new(Alphabet::latin(), false, None));
+pub static CONTEXT_SKIP_WHITESPACE: Lazy<Columnar> =
+  Lazy::new(|| Columnar::new(Alphabet::latin(), true, None));
+pub static CONTEXT_PADDING: Lazy<Columnar> =
+  Lazy::new(|| Columnar::new(Alphabet::latin(), true, Some('x')));
+
+pub static KEY_A: Lazy<ColumnarKey> =
+  Lazy::new(|| ColumnarKey::new("zebras".to_string()));
+pub static KEY_B: Lazy<ColumnarKey> =
+  Lazy::new(|| ColumnarKey::new("letter".to_string()));
+
+static LOG_TRIGRAMS: Lazy<GetConfidence> = Lazy::new(|| {
+  let table = ENGLISH.log_ngram_table(3).unwrap();
+  GetConfidence::new(Arc::new(move |text: &str| table.text_confidence(text)))
+});
+
+#[test]
+fn key_try_new() {
+  assert!(
+    ColumnarKey::try_new("zebras".to_string(), &CONTEXT_SKIP_WHITESPACE)
+      .is_ok()
+  );
+  assert!(matches!(
+    ColumnarKey::try_new("gr8ness".to_string(), &CONTEXT_SKIP_WHITESPACE),
+    Err(ParseError::InvalidChar('8')),
+  ));
+}
+
+#[test]
+fn column_order() {
+  assert_eq!(
+    CONTEXT_SKIP_WHITESPACE.column_order(&KEY_A),
+    [4, 2, 1, 3, 5, 0]
+  );
+  assert_eq!(
+    CONTEXT_SKIP_WHITESPACE.column_order(&KEY_B),
+    [1, 4, 0, 5, 2, 3]
+  );
+
+  let order = CONTEXT_SKIP_WHITESPACE.column_order(&KEY_B);
+  let key = CONTEXT_SKIP_WHITESPACE.key_from_order(&order);
+  assert_eq!(CONTEXT_SKIP_WHITESPACE.column_order(&key), order);
+}
+
+#[test]
+fn padding() {
+  let ciphertext = CONTEXT_PADDING.encipher("wearediscovered", &KEY_A);
+  assert_eq!(ciphertext, "evxacdeseroxdexwir");
+  assert_eq!(
+    CONTEXT_PADDING.decipher(&ciphertext, &KEY_A),
+    "wearediscoveredxxx"
+  );
+}
+
+#[test]
+fn keys_of_width() {
+  let keys: Vec<_> = CONTEXT_SKIP_WHITESPACE
+    .keys_of_width(3)
+    .map(|key| key.to_string())
+    .collect();
+
+  assert_eq!(keys, ["abc", "acb", "bac", "cab", "bca", "cba"]);
+}
+
+#[test]
+fn exhaustive() {
+  let ciphertext =
+    CONTEXT_SKIP_WHITESPACE.encipher(&A_TALE_OF_TWO_CITIES, &KEY_A);
+
+  let best = CONTEXT_SKIP_WHITESPACE
+    .keys_of_width(6)
+    .map(|key| {
+      let text = CONTEXT_SKIP_WHITESPACE.decipher(&ciphertext, &key);
+      (LOG_TRIGRAMS.run(&text), text)
+    })
+    .min_by(|a, b| a.0.total_cmp(&b.0))
+    .map(|(_, text)| text);
+
+  assert_eq!(best.as_deref(), Some(A_TALE_OF_TWO_CITIES.as_str()));
+}
+
+#[test]
+fn hill_climb() {
+  use crate::search::AnnealOptions;
+  use rand::{SeedableRng, rngs::StdRng};
+
+  let key = ColumnarKey::new("cryptogram".to_string());
+  let ciphertext =
+    CONTEXT_SKIP_WHITESPACE.encipher(&A_TALE_OF_TWO_CITIES, &key);
+  let options = AnnealOptions {
+    iterations: 5000,
+    temperature: 0.0,
+  };
+
+  let mut rng = StdRng::seed_from_u64(0);
+  let solved = (0..10).any(|_| {
+    let key = CONTEXT_SKIP_WHITESPACE.random_key(key.len(), &mut rng);
+    let (key, _) = CONTEXT_SKIP_WHITESPACE.hill_climb(
+      &ciphertext,
+      key,
+      &LOG_TRIGRAMS,
+      &options,
+      &mut rng,
+    );
+    CONTEXT_SKIP_WHITESPACE.decipher(&ciphertext, &key) == *A_TALE_OF_TWO_CITIES
+  });
+
+  assert!(solved);
+}
+
+cipher_test!(
+  CONTEXT_INCLUDE_WHITESPACE,
+  *QUICK_BROWN_FOX,
+  KEY_A,
+  include_whitespace
+);
+
+cipher_test!(
+  CONTEXT_SKIP_WHITESPACE,
+  *QUICK_BROWN_FOX,
+  KEY_A,
+  skip_whitespace
+);
@@ -0,0 +1,119 @@
+//! Helpers shared by the ciphers that encipher pairs of letters across
+//! two keyed Polybius squares.
+
+use crate::alphabet::Alphabet;
+use crate::language::GetConfidence;
+use crate::polybius::{ParseError, PolybiusSquare};
+use crate::search::{self, AnnealOptions};
+use rand::{Rng, RngExt, seq::SliceRandom};
+
+/// The letters of `text` found in `square`, padded with `filler` to an even
+/// length.
+pub(crate) fn letters(
+  text: &str,
+  square: &PolybiusSquare,
+  filler: char,
+) -> Vec<char> {
+  let mut letters: Vec<char> = text
+    .chars()
+    .filter(|&c| square.contains(c))
+    .map(|c| square.normalize(c))
+    .collect();
+  if letters.len() % 2 == 1 {
+    letters.push(filler);
+  }
+
+  letters
+}
+
+/// Replaces every pair `(a, b)`, with `a` at row `r1` and column `c1` of
+/// `from.0` and `b` at `r2`, `c2` of `from.1`, by the letters at `r1`, `c2`
+/// of `to.0` and `r2`, `c1` of `to.1`.
+pub(crate) fn cross(
+  letters: &[char],
+  from: (&PolybiusSquare, &PolybiusSquare),
+  to: (&PolybiusSquare, &PolybiusSquare),
+) -> String {
+  letters
+    .chunks(2)
+    .flat_map(|pair| {
+      let (Some(&a), Some(&b)) = (pair.first(), pair.get(1)) else {
+        return pair.to_vec();
+      };
+      match (from.0.position(a), from.1.position(b)) {
+        (Some((r1, c1)), Some((r2, c2))) => {
+          vec![to.0.get(r1, c2).unwrap_or(a), to.1.get(r2, c1).unwrap_or(b)]
+        }
+        _ => pair.to_vec(),
+      }
+    })
+    .collect()
+}
+
+/// Anneals a pair of squares, swapping letters within one of them, and now
+/// and then whole rows or columns, on every step.
+pub(crate) fn anneal_squares<R: Rng>(
+  squares: (PolybiusSquare, PolybiusSquare),
+  get_confidence: &GetConfidence,
+  options: &AnnealOptions,
+  rng: &mut R,
+  decipher: impl Fn(&(PolybiusSquare, PolybiusSquare)) -> String,
+) -> ((PolybiusSquare, PolybiusSquare), f64) {
+  let side = squares.0.side();
+  if side < 2 || squares.1.side() != side {
+    let confidence = get_confidence.run(&decipher(&squares));
+    return (squares, confidence);
+  }
+
+  let merge = squares.0.merge();
+  let rebuild = |letters: &[char]| {
+    // Rearranging the letters of a valid square always gives a square.
+    PolybiusSquare::from_letters(letters.to_vec(), merge)
+      .expect("letters of a square fill a square")
+  };
+
+  let initial = (squares.0.letters().to_vec(), squares.1.letters().to_vec());
+  let ((first, second), confidence) = search::anneal(
+    initial,
+    options,
+    rng,
+    |(first, second), rng| {
+      let mut squares = (first.clone(), second.clone());
+      let letters = if rng.random_bool(0.5) {
+        &mut squares.0
+      } else {
+        &mut squares.1
+      };
+      let i = rng.random_range(0..side);
+      let j = rng.random_range(0..side);
+      match rng.random_range(0..10) {
+        0 => (0..side).for_each(|k| letters.swap(i * side + k, j * side + k)),
+        1 => (0..side).for_each(|k| letters.swap(k * side + i, k * side + j)),
+        _ => {
+          let a = rng.random_range(0..letters.len());
+          let b = rng.random_range(0..letters.len());
+          letters.swap(a, b);
+        }
+      }
+      squares
+    },
+    |(first, second)| {
+      get_confidence.run(&decipher(&(rebuild(first), rebuild(second))))
+    },
+  );
+
+  ((rebuild(&first), rebuild(&second)), confidence)
+}
+
+/// A square holding the letters of `alphabet` in random order.
+pub(crate) fn random_square<R: Rng>(
+  alphabet: &Alphabet,
+  merge: Option<(char, char)>,
+  rng: &mut R,
+) -> Result<PolybiusSquare, ParseError> {
+  let square = PolybiusSquare::new("", alphabet, merge)?;
+  let mut letters = square.letters().to_vec();
+  letters.shuffle(rng);
+
+  PolybiusSquare::from_letters(letters, merge)
+}
@@ -0,0 +1,403 @@
+mod solve;
+mod wiring;
+
+use crate::{
+  alphabet::Alphabet,
+  cipher::{Decipher, Encipher},
+};
+use cipher_derive::{IntoDecipherKey, IntoEncipherKey};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+pub use solve::SolveOptions;
+pub use wiring::{Reflector, Rotor};
+
+// Letters on a rotor.
+const LETTERS: usize = 26;
+
+/// Machine settings: the reflector, the rotors from left to right with
+/// their ring settings and start positions, and the plugboard pairs. Three
+/// rotors make an Enigma I or M3; the M4 adds a thin rotor on the left and
+/// takes a thin reflector.
+#[derive(
+  Debug, Clone, Serialize, Deserialize, IntoDecipherKey, IntoEncipherKey,
+)]
+pub struct EnigmaKey {
+  reflector: Reflector,
+  rotors: Vec<Rotor>,
+  rings: Vec<usize>,
+  positions: Vec<usize>,
+  plugboard: Vec<(char, char)>,
+}
+
+/// The Enigma I, M3 and M4 rotor machines. Every letter steps the rotors
+/// before passing through the plugboard, the rotors, the reflector and back
+/// again, so enciphering and deciphering are the same. Characters other
+/// than the 26 Latin letters are left unchanged and do not step the rotors.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Enigma {
+  alphabet: Alphabet,
+}
+
+#[derive(Debug)]
+pub enum ParseError {
+  InvalidFormat,
+  InvalidReflector(String),
+  InvalidRotor(String),
+  InvalidRotorCount(usize),
+  RepeatedRotor(Rotor),
+  InvalidModel,
+  InvalidSetting(String),
+  InvalidChar(char),
+  RepeatedPlug(char),
+}
+
+impl fmt::Display for ParseError {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    match self {
+      ParseError::InvalidFormat => write!(
+        f,
+        "Expected 'reflector:rotors:rings:positions[:plugboard]', e.g. \
+         'b:i,ii,iii:aaa:aaa:ab cd'"
+      ),
+      ParseError::InvalidReflector(reflector) => {
+        write!(f, "Invalid reflector: '{reflector}'")
+      }
+      ParseError::InvalidRotor(rotor) => write!(f, "Invalid rotor: '{rotor}'"),
+      ParseError::InvalidRotorCount(count) => {
+        write!(f, "Expected 3 or 4 rotors, got {count}")
+      }
+      ParseError::RepeatedRotor(rotor) => {
+        write!(f, "Rotor {rotor} is used more than once")
+      }
+      ParseError::InvalidModel => write!(
+        f,
+        "Thin rotors and reflectors belong to the M4, which takes a thin \
+         rotor on the left of three others and a thin reflector"
+      ),
+      ParseError::InvalidSetting(setting) => write!(
+        f,
+        "Invalid setting: '{setting}', expected one letter per rotor"
+      ),
+      ParseError::InvalidChar(c) => write!(f, "Invalid character: '{c}'"),
+      ParseError::RepeatedPlug(c) => {
+        write!(f, "Letter '{c}' is plugged more than once")
+      }
+    }
+  }
+}
+
+impl EnigmaKey {
+  /// Parses `reflector:rotors:rings:positions[:plugboard]`, with the rotors
+  /// separated by commas, one ring setting and start position letter per
+  /// rotor and the plugboard as space-separated pairs of letters.
+  pub fn try_new(key: &str, context: &Enigma) -> Result<Self, ParseError> {
+    let parts: Vec<&str> = key.split(':').collect();
+    let (reflector, rotors, rings, positions, plugboard) = match parts[..] {
+      [reflector, rotors, rings, positions] => {
+        (reflector, rotors, rings, positions, "")
+      }
+      [reflector, rotors, rings, positions, plugboard] => {
+        (reflector, rotors, rings, positions, plugboard)
+      }
+      _ => return Err(ParseError::InvalidFormat),
+    };
+
+    let reflector = Reflector::from_name(reflector.trim())
+      .ok_or_else(|| ParseError::InvalidReflector(reflector.to_string()))?;
+    let rotors = rotors
+      .split(',')
+      .map(|rotor| {
+        Rotor::from_name(rotor.trim())
+          .ok_or_else(|| ParseError::InvalidRotor(rotor.to_string()))
+      })
+      .collect::<Result<_, _>>()?;
+    let setting = |setting: &str| {
+      setting
+        .trim()
+        .chars()
+        .map(|c| context.alphabet.get_index(c))
+        .collect::<Option<Vec<_>>>()
+        .ok_or_else(|| ParseError::InvalidSetting(setting.to_string()))
+    };
+    let plugboard = plugboard
+      .split_whitespace()
+      .map(|pair| match pair.chars().collect::<Vec<_>>()[..] {
+        [a, b] => Ok((a, b)),
+        _ => Err(ParseError::InvalidSetting(pair.to_string())),
+      })
+      .collect::<Result<_, _>>()?;
+
+    Self::new(
+      reflector,
+      rotors,
+      setting(rings)?,
+      setting(positions)?,
+      plugboard,
+      context,
+    )
+  }
+
+  pub fn new(
+    reflector: Reflector,
+    rotors: Vec<Rotor>,
+    rings: Vec<usize>,
+    positions: Vec<usize>,
+    plugboard: Vec<(char, char)>,
+    context: &Enigma,
+  ) -> Result<Self, ParseError> {
+    if !(3..=4).contains(&rotors.len()) {
+      return Err(ParseError::InvalidRotorCount(rotors.len()));
+    }
+    if let Some(&rotor) = rotors
+      .iter()
+      .enumerate()
+      .find(|(i, rotor)| rotors[..*i].contains(rotor))
+      .map(|(_, rotor)| rotor)
+    {
+      return Err(ParseError::RepeatedRotor(rotor));
+    }
+    let m4 = rotors.len() == 4;
+    if reflector.is_thin() != m4
+      || rotors
+        .iter()
+        .enumerate()
+        .any(|(i, r)| r.is_thin() != (m4 && i == 0))
+    {
+      return Err(ParseError::InvalidModel);
+    }
+    for setting in [&rings, &positions] {
+      if setting.len() != rotors.len() || setting.iter().any(|&i| i >= LETTERS)
+      {
+        let setting = setting
+          .iter()
+          .map(|&i| context.alphabet.get(i).unwrap_or('?'))
+          .collect();
+        return Err(ParseError::InvalidSetting(setting));
+      }
+    }
+
+    let mut plugged: Vec<char> = Vec::new();
+    for &(a, b) in &plugboard {
+      for c in [a, b] {
+        if !context.alphabet.contains(c) {
+          return Err(ParseError::InvalidChar(c));
+        }
+        if plugged.contains(&c) {
+          return Err(ParseError::RepeatedPlug(c));
+        }
+        plugged.push(c);
+      }
+    }
+
+    Ok(EnigmaKey {
+      reflector,
+      rotors,
+      rings,
+      positions,
+      plugboard,
+    })
+  }
+
+  pub fn reflector(&self) -> Reflector {
+    self.reflector
+  }
+
+  pub fn rotors(&self) -> &[Rotor] {
+    &self.rotors
+  }
+
+  pub fn rings(&self) -> &[usize] {
+    &self.rings
+  }
+
+  pub fn positions(&self) -> &[usize] {
+    &self.positions
+  }
+
+  pub fn plugboard(&self) -> &[(char, char)] {
+    &self.plugboard
+  }
+}
+
+impl TryFrom<(&str, &Enigma)> for EnigmaKey {
+  type Error = ParseError;
+
+  fn try_from((key, context): (&str, &Enigma)) -> Result<Self, Self::Error> {
+    Self::try_new(key, context)
+  }
+}
+
+impl fmt::Display for EnigmaKey {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    let letters = |setting: &[usize]| -> String {
+      setting
+        .iter()
+        .map(|&i| char::from(b'a' + i as u8))
+        .collect()
+    };
+    let rotors: Vec<String> =
+      self.rotors.iter().map(|r| r.to_string()).collect();
+    let plugboard: Vec<String> = self
+      .plugboard
+      .iter()
+      .map(|(a, b)| format!("{a}{b}"))
+      .collect();
+
+    write!(
+      f,
+      "{}:{}:{}:{}",
+      self.reflector,
+      rotors.join(","),
+      letters(&self.rings),
+      letters(&self.positions)
+    )?;
+    if !plugboard.is_empty() {
+      write!(f, ":{}", plugboard.join(" "))?;
+    }
+
+    Ok(())
+  }
+}
+
+/// The wiring of a key as index tables, with the rotors in their current
+/// positions.
+struct Machine {
+  plugboard: [usize; LETTERS],
+  forward: Vec<[usize; LETTERS]>,
+  backward: Vec<[usize; LETTERS]>,
+  notches: Vec<Vec<usize>>,
+  reflector: [usize; LETTERS],
+  rings: Vec<usize>,
+  positions: Vec<usize>,
+}
+
+impl Machine {
+  fn new(key: &EnigmaKey, alphabet: &Alphabet) -> Self {
+    let table = |wiring: &str| -> [usize; LETTERS] {
+      let mut table = [0; LETTERS];
+      for (i, c) in wiring.chars().enumerate() {
+        table[i] = alphabet.get_index(c).unwrap_or(i);
+      }
+      table
+    };
+    let inverse = |table: [usize; LETTERS]| -> [usize; LETTERS] {
+      let mut inverse = [0; LETTERS];
+      for (i, &j) in table.iter().enumerate() {
+        inverse[j] = i;
+      }
+      inverse
+    };
+
+    let mut plugboard: [usize; LETTERS] = std::array::from_fn(|i| i);
+    for &(a, b) in &key.plugboard {
+      if let (Some(a), Some(b)) = (alphabet.get_index(a), alphabet.get_index(b))
+      {
+        plugboard.swap(a, b);
+      }
+    }
+    let forward: Vec<_> =
+      key.rotors.iter().map(|r| table(r.wiring())).collect();
+
+    Machine {
+      plugboard,
+      backward: forward.iter().map(|&table| inverse(table)).collect(),
+      forward,
+      notches: key
+        .rotors
+        .iter()
+        .map(|r| {
+          r.notches()
+            .chars()
+            .filter_map(|c| alphabet.get_index(c))
+            .collect()
+        })
+        .collect(),
+      reflector: table(key.reflector.wiring()),
+      rings: key.rings.clone(),
+      positions: key.positions.clone(),
+    }
+  }
+
+  /// Steps the three rightmost rotors. The middle rotor steps again along
+  /// with the left one when it reaches its own notch, the double step.
+  fn step(&mut self) {
+    let right = self.positions.len() - 1;
+    let (middle, left) = (right - 1, right - 2);
+    let at_notch =
+      |rotor: usize| self.notches[rotor].contains(&self.positions[rotor]);
+
+    if at_notch(middle) {
+      self.positions[middle] += 1;
+      self.positions[left] += 1;
+    } else if at_notch(right) {
+      self.positions[middle] += 1;
+    }
+    self.positions[right] += 1;
+
+    for position in [left, middle, right] {
+      self.positions[position] %= LETTERS;
+    }
+  }
+
+  fn through(&self, table: &[usize; LETTERS], rotor: usize, c: usize) -> usize {
+    let shift = self.positions[rotor] + LETTERS - self.rings[rotor];
+    (table[(c + shift) % LETTERS] + LETTERS - shift % LETTERS) % LETTERS
+  }
+
+  fn encipher(&mut self, c: usize) -> usize {
+    self.step();
+
+    let mut c = self.plugboard[c];
+    for rotor in (0..self.forward.len()).rev() {
+      c = self.through(&self.forward[rotor], rotor, c);
+    }
+    c = self.reflector[c];
+    for rotor in 0..self.backward.len() {
+      c = self.through(&self.backward[rotor], rotor, c);
+    }
+
+    self.plugboard[c]
+  }
+}
+
+impl Enigma {
+  pub fn new() -> Self {
+    Enigma {
+      alphabet: Alphabet::latin(),
+    }
+  }
+}
+
+impl Default for Enigma {
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+impl Encipher for Enigma {
+  type Key = EnigmaKey;
+
+  fn encipher(&self, plaintext: &str, key: &Self::Key) -> String {
+    let mut machine = Machine::new(key, &self.alphabet);
+
+    plaintext
+      .chars()
+      .map(|c| match self.alphabet.get_index(c) {
+        Some(i) => self.alphabet.get(machine.encipher(i)).unwrap_or(c),
+        None => c,
+      })
+      .collect()
+  }
+}
+
+impl Decipher for Enigma {
+  type Key = EnigmaKey;
+
+  fn decipher(&self, ciphertext: &str, key: &Self::Key) -> String {
+    self.encipher(ciphertext, key)
+  }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,282 @@
+use super::{Enigma, EnigmaKey, LETTERS, Machine, Reflector, Rotor};
+use crate::cipher::Decipher;
+use crate::language::GetConfidence;
+use crate::periodicity;
+use crate::search::{self, AnnealOptions};
+use ahash::AHashSet;
+use itertools::Itertools;
+use rand::{Rng, RngExt, SeedableRng, rngs::StdRng};
+use rayon::prelude::*;
+use std::sync::Mutex;
+
+// Most plugboard cables a machine could take.
+const MAX_PLUGS: usize = 13;
+
+/// How widely `Enigma::solve` searches.
+#[derive(Debug, Clone)]
+pub struct SolveOptions {
+  /// Start positions kept for every rotor order.
+  pub candidates: usize,
+  /// Candidates with the best index of coincidence, once plugged, whose
+  /// plugboard is then refined on n-grams.
+  pub finalists: usize,
+  /// Plugboard hill climbs on n-grams from every finalist.
+  pub restarts: usize,
+  pub anneal: AnnealOptions,
+}
+
+impl Enigma {
+  /// Searches for the key of `ciphertext` among `orders` of rotors, running
+  /// in parallel on the current rayon pool. Start positions, rings and a
+  /// first plugboard are found on the index of coincidence, then the
+  /// plugboard of the best of them on `get_confidence`. Every key found is
+  /// passed to `found` with its confidence as soon as it is, once even when
+  /// several restarts reach it.
+  ///
+  /// Each step draws its own generator from `rng`, so a seeded `rng` gives
+  /// the same keys however the steps are scheduled.
+  #[allow(clippy::too_many_arguments)]
+  pub fn solve<R: Rng>(
+    &self,
+    ciphertext: &str,
+    reflector: Reflector,
+    orders: &[Vec<Rotor>],
+    get_confidence: &GetConfidence,
+    options: &SolveOptions,
+    rng: &mut R,
+    found: impl Fn(EnigmaKey, f64) + Sync,
+  ) {
+    let positions: Vec<_> = orders
+      .par_iter()
+      .flat_map_iter(|order| {
+        self.search_positions(ciphertext, reflector, order, options.candidates)
+      })
+      .collect();
+    let seeds: Vec<u64> = positions.iter().map(|_| rng.random()).collect();
+    let mut candidates: Vec<_> = positions
+      .into_par_iter()
+      .zip(seeds)
+      .map(|((key, _), seed)| {
+        let (key, _) = self.ring_search(ciphertext, key);
+        self.plugboard_ioc_hill_climb(
+          ciphertext,
+          key,
+          &options.anneal,
+          &mut StdRng::seed_from_u64(seed),
+        )
+      })
+      .collect();
+    candidates.sort_by(|a, b| b.1.total_cmp(&a.1));
+    candidates.truncate(options.finalists);
+
+    let restarts: Vec<_> = candidates
+      .into_iter()
+      .flat_map(|(key, _)| (0..options.restarts).map(move |_| key.clone()))
+      .map(|key| (key, rng.random::<u64>()))
+      .collect();
+    // Restarts often reach the same key, which should be passed on once.
+    let seen = Mutex::new(AHashSet::new());
+    restarts.into_par_iter().for_each(|(key, seed)| {
+      let (key, confidence) = self.plugboard_hill_climb(
+        ciphertext,
+        key,
+        get_confidence,
+        &options.anneal,
+        &mut StdRng::seed_from_u64(seed),
+      );
+      let new = seen
+        .lock()
+        .map(|mut seen| seen.insert(key.to_string()))
+        .unwrap_or(true);
+      if new {
+        found(key, confidence);
+      }
+    });
+  }
+
+  /// Every order of three different rotors out of `rotors`, leaving out the
+  /// thin rotors.
+  pub fn rotor_orders(&self, rotors: &[Rotor]) -> Vec<Vec<Rotor>> {
+    rotors
+      .iter()
+      .copied()
+      .filter(|rotor| !rotor.is_thin())
+      .unique()
+      .permutations(3)
+      .collect()
+  }
+
+  /// Index of coincidence of `ciphertext` deciphered with `key`.
+  pub fn index_of_coincidence(&self, ciphertext: &str, key: &EnigmaKey) -> f64 {
+    self.letters_index_of_coincidence(&self.letters(ciphertext), key)
+  }
+
+  /// Tries every start position of `rotors` with the rings at A and no
+  /// plugboard, and returns the `candidates` keys whose decipherment has
+  /// the highest index of coincidence, best first. The plugboard leaves
+  /// most letters alone, so the right positions already read much closer
+  /// to the language than chance.
+  pub fn search_positions(
+    &self,
+    ciphertext: &str,
+    reflector: Reflector,
+    rotors: &[Rotor],
+    candidates: usize,
+  ) -> Vec<(EnigmaKey, f64)> {
+    let letters = self.letters(ciphertext);
+    let key = EnigmaKey {
+      reflector,
+      rotors: rotors.to_vec(),
+      rings: vec![0; rotors.len()],
+      positions: vec![0; rotors.len()],
+      plugboard: Vec::new(),
+    };
+
+    (0..rotors.len())
+      .map(|_| 0..LETTERS)
+      .multi_cartesian_product()
+      .map(|positions| {
+        let key = EnigmaKey {
+          positions,
+          ..key.clone()
+        };
+        let ioc = self.letters_index_of_coincidence(&letters, &key);
+        (key, ioc)
+      })
+      .k_largest_by(candidates, |a, b| a.1.total_cmp(&b.1))
+      .collect()
+  }
+
+  /// Tries every ring setting of the right and then the middle rotor,
+  /// turning the rotor with its ring so the wiring keeps its place and only
+  /// the turnover moves, and keeps the setting with the highest index of
+  /// coincidence.
+  pub fn ring_search(
+    &self,
+    ciphertext: &str,
+    key: EnigmaKey,
+  ) -> (EnigmaKey, f64) {
+    let letters = self.letters(ciphertext);
+    let right = key.rotors.len() - 1;
+    let initial = self.letters_index_of_coincidence(&letters, &key);
+
+    [right, right - 1]
+      .into_iter()
+      .fold((key, initial), |best, rotor| {
+        let start = best.0.clone();
+        (1..LETTERS)
+          .map(|offset| {
+            let mut key = start.clone();
+            key.rings[rotor] = (key.rings[rotor] + offset) % LETTERS;
+            key.positions[rotor] = (key.positions[rotor] + offset) % LETTERS;
+            let ioc = self.letters_index_of_coincidence(&letters, &key);
+            (key, ioc)
+          })
+          .fold(best, |best, x| if x.1 > best.1 { x } else { best })
+      })
+  }
+
+  /// Improves the plugboard of `key` by plugging, unplugging and moving
+  /// cables, keeping changes that raise the index of coincidence of the
+  /// deciphered text. Much cheaper than scoring n-grams, this finds most of
+  /// the cables to start `plugboard_hill_climb` from.
+  pub fn plugboard_ioc_hill_climb<R: Rng>(
+    &self,
+    ciphertext: &str,
+    key: EnigmaKey,
+    options: &AnnealOptions,
+    rng: &mut R,
+  ) -> (EnigmaKey, f64) {
+    let letters = self.letters(ciphertext);
+    let (plugboard, score) = search::anneal(
+      key.plugboard.clone(),
+      options,
+      rng,
+      |plugboard, rng| self.move_cable(plugboard, rng),
+      |plugboard| {
+        let key = EnigmaKey {
+          plugboard: plugboard.clone(),
+          ..key.clone()
+        };
+        -self.letters_index_of_coincidence(&letters, &key)
+      },
+    );
+
+    (EnigmaKey { plugboard, ..key }, -score)
+  }
+
+  /// Improves the plugboard of `key` by plugging, unplugging and moving
+  /// cables, keeping changes that lower the confidence score of the
+  /// deciphered text.
+  pub fn plugboard_hill_climb<R: Rng>(
+    &self,
+    ciphertext: &str,
+    key: EnigmaKey,
+    get_confidence: &GetConfidence,
+    options: &AnnealOptions,
+    rng: &mut R,
+  ) -> (EnigmaKey, f64) {
+    let (plugboard, confidence) = search::anneal(
+      key.plugboard.clone(),
+      options,
+      rng,
+      |plugboard, rng| self.move_cable(plugboard, rng),
+      |plugboard| {
+        let key = EnigmaKey {
+          plugboard: plugboard.clone(),
+          ..key.clone()
+        };
+        get_confidence.run(&self.decipher(ciphertext, &key))
+      },
+    );
+
+    (EnigmaKey { plugboard, ..key }, confidence)
+  }
+
+  /// Plugs two random letters together, unplugging whatever they were
+  /// plugged to, or unplugs them if they already were.
+  fn move_cable<R: Rng>(
+    &self,
+    plugboard: &[(char, char)],
+    rng: &mut R,
+  ) -> Vec<(char, char)> {
+    let mut plugboard = plugboard.to_vec();
+    let a = self
+      .alphabet
+      .get(rng.random_range(0..LETTERS))
+      .unwrap_or('a');
+    let b = self
+      .alphabet
+      .get(rng.random_range(0..LETTERS))
+      .unwrap_or('a');
+    if a == b {
+      return plugboard;
+    }
+
+    let together = plugboard.contains(&(a, b)) || plugboard.contains(&(b, a));
+    plugboard.retain(|&(x, y)| ![x, y].contains(&a) && ![x, y].contains(&b));
+    if !together && plugboard.len() < MAX_PLUGS {
+      plugboard.push((a, b));
+    }
+
+    plugboard
+  }
+
+  fn letters(&self, text: &str) -> Vec<usize> {
+    text
+      .chars()
+      .filter_map(|c| self.alphabet.get_index(c))
+      .collect()
+  }
+
+  fn letters_index_of_coincidence(
+    &self,
+    letters: &[usize],
+    key: &EnigmaKey,
+  ) -> f64 {
+    let mut machine = Machine::new(key, &self.alphabet);
+    let plaintext = letters.iter().map(|&c| machine.encipher(c));
+
+    periodicity::index_of_coincidence(plaintext, LETTERS)
+  }
+}
@@ -0,0 +1,269 @@
+use super::*;
+use crate::cipher_test;
+use crate::tests::*;
+use once_cell::sync::Lazy;
+
+pub static CONTEXT: Lazy<Enigma> = Lazy::new(Enigma::new);
+
+pub static KEY_I_II_III: Lazy<EnigmaKey> =
+  Lazy::new(|| EnigmaKey::try_new("b:i,ii,iii:aaa:aaa", &CONTEXT).unwrap());
+
+// Operation Barbarossa, 1941.
+pub static KEY_BARBAROSSA: Lazy<EnigmaKey> = Lazy::new(|| {
+  EnigmaKey::try_new(
+    "b:ii,iv,v:bul:bla:av bs cg dl fu hz in km ow rx",
+    &CONTEXT,
+  )
+  .unwrap()
+});
+
+// U-264, Kapitänleutnant Hartwig Looks, 1942.
+pub static KEY_U264: Lazy<EnigmaKey> = Lazy::new(|| {
+  EnigmaKey::try_new(
+    "bthin:beta,ii,iv,i:aaav:vjna:at bl df gj hm nw op qy rz vx",
+    &CONTEXT,
+  )
+  .unwrap()
+});
+
+#[test]
+fn key_try_new() {
+  assert_eq!(
+    KEY_BARBAROSSA.to_string(),
+    "b:ii,iv,v:bul:bla:av bs cg dl fu hz in km ow rx"
+  );
+  assert!(matches!(
+    EnigmaKey::try_new("b:i,ii,iii", &CONTEXT),
+    Err(ParseError::InvalidFormat)
+  ));
+  assert!(matches!(
+    EnigmaKey::try_new("d:i,ii,iii:aaa:aaa", &CONTEXT),
+    Err(ParseError::InvalidReflector(_))
+  ));
+  assert!(matches!(
+    EnigmaKey::try_new("b:i,ii,ix:aaa:aaa", &CONTEXT),
+    Err(ParseError::InvalidRotor(_))
+  ));
+  assert!(matches!(
+    EnigmaKey::try_new("b:i,ii:aa:aa", &CONTEXT),
+    Err(ParseError::InvalidRotorCount(2))
+  ));
+  assert!(matches!(
+    EnigmaKey::try_new("b:i,ii,i:aaa:aaa", &CONTEXT),
+    Err(ParseError::RepeatedRotor(Rotor::I))
+  ));
+  assert!(matches!(
+    EnigmaKey::try_new("b:beta,i,ii,iii:aaaa:aaaa", &CONTEXT),
+    Err(ParseError::InvalidModel)
+  ));
+  assert!(matches!(
+    EnigmaKey::try_new("bthin:i,ii,iii:aaa:aaa", &CONTEXT),
+    Err(ParseError::InvalidModel)
+  ));
+  assert!(matches!(
+    EnigmaKey::try_new("b:i,ii,iii:aa:aaa", &CONTEXT),
+    Err(ParseError::InvalidSetting(_))
+  ));
+  assert!(matches!(
+    EnigmaKey::try_new("b:i,ii,iii:aaa:aaa:ab bc", &CONTEXT),
+    Err(ParseError::RepeatedPlug('b'))
+  ));
+}
+
+#[test]
+fn stepping() {
+  let mut machine = Machine::new(
+    &EnigmaKey::try_new("b:i,ii,iii:aaa:adu", &CONTEXT).unwrap(),
+    &CONTEXT.alphabet,
+  );
+  let positions: Vec<String> = (0..3)
+    .map(|_| {
+      machine.step();
+      machine
+        .positions
+        .iter()
+        .map(|&i| CONTEXT.alphabet.get(i).unwrap())
+        .collect()
+    })
+    .collect();
+
+  // The middle rotor steps twice in a row.
+  assert_eq!(positions, ["adv", "aew", "bfx"]);
+}
+
+#[test]
+fn enigma_i() {
+  assert_eq!(CONTEXT.encipher("aaaaa", &KEY_I_II_III), "bdzgo");
+}
+
+#[test]
+fn barbarossa() {
+  let ciphertext = "edpud nrgys zrcxn uytpo mrmbo fktbz rezkm lxlve fguey \
+                    siozv eqmik ubpmm ylklt tdeis mdica gykua ctcdo mohwx \
+                    muuia ubsts lrnbz szwnr fxwfy ssxjz vijhi dishp rklka \
+                    yupad txqsp inqma tlpif svkda sctac dpbop vhjk";
+  let plaintext = "aufkl xabte ilung xvonx kurti nowax kurti nowax nordw \
+                   estlx sebez xsebe zxuaf flieg erstr aszer iqtun gxdub \
+                   rowki xdubr owkix opots chkax opots chkax umxei nsaqt \
+                   drein ullxu hrang etret enxan griff xinfx rgtx";
+
+  assert_eq!(CONTEXT.decipher(ciphertext, &KEY_BARBAROSSA), plaintext);
+}
+
+#[test]
+fn u264() {
+  let ciphertext = "nczwvusxpnyminhzxmqxsfwxwlkjahshnmcoccakuqpmkcsmhkseinjusblkiosxckubhmllxcsjusrrdvkohulxwccbgvliyxeoahxrhkkfvdrewezlxobafgyujqukgrtvukameurbveksuhhvoyhabcjwmaklfklmyfvnrizrvvrtkofdanjmolbgffleoprgtflvrhowopbekvwmuqfmpwparmfhagkxiibg";
+
+  assert!(
+    CONTEXT
+      .decipher(ciphertext, &KEY_U264)
+      .starts_with("vonvonjlooksjhffttteinseinsdreizwoyyqnnsneuninhaltxx")
+  );
+}
+
+// The start of the tale, with only the letters the machine enciphers.
+static TALE: Lazy<String> = Lazy::new(|| {
+  A_TALE_OF_TWO_CITIES
+    .chars()
+    .filter(|c| c.is_ascii_lowercase())
+    .take(400)
+    .collect()
+});
+
+#[test]
+fn rotor_orders() {
+  assert_eq!(CONTEXT.rotor_orders(&Rotor::ALL[..5]).len(), 60);
+  assert_eq!(CONTEXT.rotor_orders(&Rotor::ALL).len(), 336);
+}
+
+#[test]
+fn search() {
+  let key =
+    EnigmaKey::try_new("b:ii,iv,v:aad:blz:av bs cg dl fu", &CONTEXT).unwrap();
+  let ciphertext = CONTEXT.encipher(&TALE, &key);
+
+  let candidates =
+    CONTEXT.search_positions(&ciphertext, Reflector::B, &key.rotors, 3);
+  // The rings at A turn the right rotor back by the ring setting.
+  assert_eq!(candidates[0].0.positions(), [1, 11, 22]);
+
+  let (found, _) = CONTEXT.ring_search(&ciphertext, candidates[0].0.clone());
+  let found = EnigmaKey {
+    plugboard: key.plugboard.clone(),
+    ..found
+  };
+  assert_eq!(CONTEXT.decipher(&ciphertext, &found), *TALE);
+}
+
+#[test]
+fn plugboard_hill_climb() {
+  use crate::language::GetConfidence;
+  use crate::search::AnnealOptions;
+  use rand::{SeedableRng, rngs::StdRng};
+  use std::sync::Arc;
+
+  let ciphertext = CONTEXT.encipher(&TALE, &KEY_BARBAROSSA);
+  let table = ENGLISH.log_ngram_table(3).unwrap();
+  let get_confidence =
+    GetConfidence::new(Arc::new(move |text: &str| table.text_confidence(text)));
+  let options = AnnealOptions {
+    iterations: 3000,
+    temperature: 0.0,
+  };
+  let key = EnigmaKey {
+    plugboard: Vec::new(),
+    ..KEY_BARBAROSSA.clone()
+  };
+
+  let mut rng = StdRng::seed_from_u64(0);
+  let (key, _) = CONTEXT.plugboard_hill_climb(
+    &ciphertext,
+    key,
+    &get_confidence,
+    &options,
+    &mut rng,
+  );
+
+  assert_eq!(CONTEXT.decipher(&ciphertext, &key), *TALE);
+}
+
+#[test]
+fn plugboard_ioc_hill_climb() {
+  use crate::search::AnnealOptions;
+  use rand::{SeedableRng, rngs::StdRng};
+
+  let ciphertext = CONTEXT.encipher(&TALE, &KEY_BARBAROSSA);
+  let options = AnnealOptions {
+    iterations: 3000,
+    temperature: 0.0,
+  };
+  let key = EnigmaKey {
+    plugboard: Vec::new(),
+    ..KEY_BARBAROSSA.clone()
+  };
+  let unplugged = CONTEXT.index_of_coincidence(&ciphertext, &key);
+
+  let mut rng = StdRng::seed_from_u64(0);
+  let (key, ioc) =
+    CONTEXT.plugboard_ioc_hill_climb(&ciphertext, key, &options, &mut rng);
+
+  assert_eq!(ioc, CONTEXT.index_of_coincidence(&ciphertext, &key));
+  assert!(ioc > unplugged);
+  let right = key
+    .plugboard
+    .iter()
+    .filter(|&&(a, b)| {
+      let cables = &KEY_BARBAROSSA.plugboard;
+      cables.contains(&(a, b)) || cables.contains(&(b, a))
+    })
+    .count();
+  assert!(right >= 5, "{key}");
+}
+
+// The whole search, over the order of the key and its reverse.
+#[test]
+fn solve() {
+  use crate::language::GetConfidence;
+  use crate::search::AnnealOptions;
+  use itertools::Itertools;
+  use rand::{SeedableRng, rngs::StdRng};
+  use std::sync::{Arc, Mutex};
+
+  let key =
+    EnigmaKey::try_new("b:ii,iv,v:aad:blz:av bs cg dl fu", &CONTEXT).unwrap();
+  let ciphertext = CONTEXT.encipher(&TALE, &key);
+  let table = ENGLISH.log_ngram_table(3).unwrap();
+  let get_confidence =
+    GetConfidence::new(Arc::new(move |text: &str| table.text_confidence(text)));
+  let options = SolveOptions {
+    candidates: 3,
+    finalists: 1,
+    restarts: 2,
+    anneal: AnnealOptions {
+      iterations: 3000,
+      temperature: 0.0,
+    },
+  };
+  let orders = [
+    vec![Rotor::II, Rotor::IV, Rotor::V],
+    vec![Rotor::V, Rotor::IV, Rotor::II],
+  ];
+
+  let found = Mutex::new(Vec::new());
+  CONTEXT.solve(
+    &ciphertext,
+    Reflector::B,
+    &orders,
+    &get_confidence,
+    &options,
+    &mut StdRng::seed_from_u64(0),
+    |key, confidence| found.lock().unwrap().push((key, confidence)),
+  );
+  let found = found.into_inner().unwrap();
+  let (best, _) = found.iter().min_by(|a, b| a.1.total_cmp(&b.1)).unwrap();
+
+  assert!(found.iter().map(|(key, _)| key.to_string()).all_unique());
+  assert_eq!(CONTEXT.decipher(&ciphertext, best), *TALE);
+}
+
+cipher_test!(CONTEXT, *QUICK_BROWN_FOX, KEY_BARBAROSSA, quick_brown_fox);
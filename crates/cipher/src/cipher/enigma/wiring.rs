@@ -0,0 +1,141 @@
+//! Wiring of the historical rotors and reflectors, written as the letters
+//! that A to Z are wired to.
+
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Rotor {
+  I,
+  II,
+  III,
+  IV,
+  V,
+  VI,
+  VII,
+  VIII,
+  /// Thin fourth rotor of the M4, which never steps.
+  Beta,
+  /// Thin fourth rotor of the M4, which never steps.
+  Gamma,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Reflector {
+  B,
+  C,
+  /// Thin reflector of the M4, used with Beta or Gamma.
+  BThin,
+  /// Thin reflector of the M4, used with Beta or Gamma.
+  CThin,
+}
+
+impl Rotor {
+  pub const ALL: [Rotor; 10] = [
+    Rotor::I,
+    Rotor::II,
+    Rotor::III,
+    Rotor::IV,
+    Rotor::V,
+    Rotor::VI,
+    Rotor::VII,
+    Rotor::VIII,
+    Rotor::Beta,
+    Rotor::Gamma,
+  ];
+
+  /// Looks a rotor up by its name as displayed, e.g. "iv" or "beta".
+  pub fn from_name(name: &str) -> Option<Rotor> {
+    Rotor::ALL.into_iter().find(|r| r.to_string() == name)
+  }
+
+  pub fn wiring(&self) -> &'static str {
+    match self {
+      Rotor::I => "ekmflgdqvzntowyhxuspaibrcj",
+      Rotor::II => "ajdksiruxblhwtmcqgznpyfvoe",
+      Rotor::III => "bdfhjlcprtxvznyeiwgakmusqo",
+      Rotor::IV => "esovpzjayquirhxlnftgkdcmwb",
+      Rotor::V => "vzbrgityupsdnhlxawmjqofeck",
+      Rotor::VI => "jpgvoumfyqbenhzrdkasxlictw",
+      Rotor::VII => "nzjhgrcxmyswboufaivlpekqdt",
+      Rotor::VIII => "fkqhtlxocbjspdzramewniugyv",
+      Rotor::Beta => "leyjvcnixwpbqmdrtakzgfuhos",
+      Rotor::Gamma => "fsokanuerhmbtiycwlqpzxvgjd",
+    }
+  }
+
+  /// Positions at which the rotor turns its left neighbour over as it
+  /// steps.
+  pub fn notches(&self) -> &'static str {
+    match self {
+      Rotor::I => "q",
+      Rotor::II => "e",
+      Rotor::III => "v",
+      Rotor::IV => "j",
+      Rotor::V => "z",
+      Rotor::VI | Rotor::VII | Rotor::VIII => "zm",
+      Rotor::Beta | Rotor::Gamma => "",
+    }
+  }
+
+  pub fn is_thin(&self) -> bool {
+    matches!(self, Rotor::Beta | Rotor::Gamma)
+  }
+}
+
+impl Reflector {
+  pub const ALL: [Reflector; 4] = [
+    Reflector::B,
+    Reflector::C,
+    Reflector::BThin,
+    Reflector::CThin,
+  ];
+
+  /// Looks a reflector up by its name as displayed, e.g. "b" or "cthin".
+  pub fn from_name(name: &str) -> Option<Reflector> {
+    Reflector::ALL.into_iter().find(|r| r.to_string() == name)
+  }
+
+  pub fn wiring(&self) -> &'static str {
+    match self {
+      Reflector::B => "yruhqsldpxngokmiebfzcwvjat",
+      Reflector::C => "fvpjiaoyedrzxwgctkuqsbnmhl",
+      Reflector::BThin => "enkqauywjicopblmdxzvfthrgs",
+      Reflector::CThin => "rdobjntkvehmlfcwzaxgyipsuq",
+    }
+  }
+
+  pub fn is_thin(&self) -> bool {
+    matches!(self, Reflector::BThin | Reflector::CThin)
+  }
+}
+
+impl fmt::Display for Rotor {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    let name = match self {
+      Rotor::I => "i",
+      Rotor::II => "ii",
+      Rotor::III => "iii",
+      Rotor::IV => "iv",
+      Rotor::V => "v",
+      Rotor::VI => "vi",
+      Rotor::VII => "vii",
+      Rotor::VIII => "viii",
+      Rotor::Beta => "beta",
+      Rotor::Gamma => "gamma",
+    };
+    write!(f, "{name}")
+  }
+}
+
+impl fmt::Display for Reflector {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    let name = match self {
+      Reflector::B => "b",
+      Reflector::C => "c",
+      Reflector::BThin => "bthin",
+      Reflector::CThin => "cthin",
+    };
+    write!(f, "{name}")
+  }
+}
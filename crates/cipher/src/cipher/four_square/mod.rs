@@ -0,0 +1,141 @@
+use crate::{
+  alphabet::Alphabet,
+  cipher::{Decipher, Encipher, digraphic},
+  language::GetConfidence,
+  polybius::{ParseError, PolybiusSquare},
+  search::AnnealOptions,
+};
+use cipher_derive::{IntoDecipherKey, IntoEncipherKey};
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// The keyed squares at the top right and bottom left, written as their
+/// keywords separated by a comma.
+#[derive(
+  Debug, Clone, Serialize, Deserialize, IntoDecipherKey, IntoEncipherKey,
+)]
+pub struct FourSquareKey(PolybiusSquare, PolybiusSquare);
+
+/// Looks pairs of letters up in two plain squares at the top left and
+/// bottom right and replaces them by the letters at the other corners of
+/// their rectangle, in the keyed squares. Letters not in the squares are
+/// dropped and an odd final letter is padded with `filler`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FourSquare {
+  alphabet: Alphabet,
+  merge: Option<(char, char)>,
+  filler: char,
+}
+
+impl FourSquareKey {
+  pub fn try_new(key: &str, context: &FourSquare) -> Result<Self, ParseError> {
+    let (first, second) = key.split_once(',').unwrap_or((key, ""));
+    let square = |keyword: &str| {
+      PolybiusSquare::new(keyword.trim(), &context.alphabet, context.merge)
+    };
+
+    Ok(FourSquareKey::new(square(first)?, square(second)?))
+  }
+
+  pub fn new(first: PolybiusSquare, second: PolybiusSquare) -> Self {
+    FourSquareKey(first, second)
+  }
+}
+
+impl TryFrom<(&str, &FourSquare)> for FourSquareKey {
+  type Error = ParseError;
+
+  fn try_from(
+    (key, context): (&str, &FourSquare),
+  ) -> Result<Self, Self::Error> {
+    Self::try_new(key, context)
+  }
+}
+
+impl fmt::Display for FourSquareKey {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(f, "{},{}", self.0, self.1)
+  }
+}
+
+impl FourSquare {
+  pub fn new(
+    alphabet: Alphabet,
+    merge: Option<(char, char)>,
+    filler: char,
+  ) -> Self {
+    FourSquare {
+      alphabet,
+      merge,
+      filler,
+    }
+  }
+
+  fn plain_square(&self) -> Result<PolybiusSquare, ParseError> {
+    PolybiusSquare::new("", &self.alphabet, self.merge)
+  }
+
+  pub fn random_key<R: Rng>(
+    &self,
+    rng: &mut R,
+  ) -> Result<FourSquareKey, ParseError> {
+    Ok(FourSquareKey::new(
+      digraphic::random_square(&self.alphabet, self.merge, rng)?,
+      digraphic::random_square(&self.alphabet, self.merge, rng)?,
+    ))
+  }
+
+  /// Improves `key` by rearranging the letters of its squares, keeping
+  /// changes that lower the confidence score of the deciphered text.
+  pub fn hill_climb<R: Rng>(
+    &self,
+    ciphertext: &str,
+    key: FourSquareKey,
+    get_confidence: &GetConfidence,
+    options: &AnnealOptions,
+    rng: &mut R,
+  ) -> (FourSquareKey, f64) {
+    let ((first, second), confidence) = digraphic::anneal_squares(
+      (key.0, key.1),
+      get_confidence,
+      options,
+      rng,
+      |(first, second)| {
+        let key = FourSquareKey::new(first.clone(), second.clone());
+        self.decipher(ciphertext, &key)
+      },
+    );
+
+    (FourSquareKey::new(first, second), confidence)
+  }
+}
+
+impl Encipher for FourSquare {
+  type Key = FourSquareKey;
+
+  fn encipher(&self, plaintext: &str, key: &Self::Key) -> String {
+    let Ok(plain) = self.plain_square() else {
+      return plaintext.to_string();
+    };
+    let letters = digraphic::letters(plaintext, &plain, self.filler);
+
+    digraphic::cross(&letters, (&plain, &plain), (&key.0, &key.1))
+  }
+}
+
+impl Decipher for FourSquare {
+  type Key = FourSquareKey;
+
+  fn decipher(&self, ciphertext: &str, key: &Self::Key) -> String {
+    let Ok(plain) = self.plain_square() else {
+      return ciphertext.to_string();
+    };
+    let letters = digraphic::letters(ciphertext, &plain, self.filler);
+
+    digraphic::cross(&letters, (&key.0, &key.1), (&plain, &plain))
+  }
+}
+
+#[cfg(test)]
+mod tests;
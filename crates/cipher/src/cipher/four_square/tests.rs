@@ -0,0 +1,94 @@
+use super::*;
+use crate::alphabet::Alphabet;
+use crate::cipher_test;
+use crate::language::GetConfidence;
+use crate::tests::*;
+use once_cell::sync::Lazy;
+use std::sync::Arc;
+
+pub static CONTEXT: Lazy<FourSquare> =
+  Lazy::new(|| FourSquare::new(Alphabet::latin(), Some(('j', 'i')), 'x'));
+
+pub static KEY_A: Lazy<FourSquareKey> =
+  Lazy::new(|| FourSquareKey::try_new("example,keyword", &CONTEXT).unwrap());
+
+static HELP_ME: Lazy<String> = Lazy::new(|| "helpmeobiwankenobi".to_string());
+
+#[test]
+fn key_try_new() {
+  assert_eq!(
+    KEY_A.to_string(),
+    "examplbcdfghiknoqrstuvwyz,keywordabcfghilmnpqstuvxz"
+  );
+  assert!(matches!(
+    FourSquareKey::try_new("example,gr8", &CONTEXT),
+    Err(ParseError::InvalidChar('8')),
+  ));
+}
+
+#[test]
+fn wikipedia_example() {
+  // The example squares leave out Q instead of merging J into I.
+  let context = FourSquare::new(
+    Alphabet::from_iter("abcdefghijklmnoprstuvwxyz".chars()),
+    None,
+    'x',
+  );
+  let key = FourSquareKey::try_new("example,keyword", &context).unwrap();
+
+  assert_eq!(
+    context.encipher("help me obi wan kenobi", &key),
+    "fygmkyhobxmfkkkimd"
+  );
+}
+
+#[test]
+fn hill_climb() {
+  use rand::{SeedableRng, rngs::StdRng};
+
+  let ciphertext = CONTEXT.encipher(&A_TALE_OF_TWO_CITIES, &KEY_A);
+  let key = FourSquareKey::new(
+    swapped(&KEY_A.0, &[(0, 6)]),
+    swapped(&KEY_A.1, &[(2, 13)]),
+  );
+  let table = ENGLISH.log_ngram_table(3).unwrap();
+  let get_confidence =
+    GetConfidence::new(Arc::new(move |text: &str| table.text_confidence(text)));
+  let options = AnnealOptions {
+    iterations: 2000,
+    temperature: 0.0,
+  };
+
+  let start = get_confidence.run(&CONTEXT.decipher(&ciphertext, &key));
+
+  let mut rng = StdRng::seed_from_u64(0);
+  let (key, confidence) =
+    CONTEXT.hill_climb(&ciphertext, key, &get_confidence, &options, &mut rng);
+
+  // Annealing only rearranges the letters of each square, and never ends
+  // on a key worse than the one it started from.
+  assert_eq!(sorted(&key.0), sorted(&KEY_A.0));
+  assert_eq!(sorted(&key.1), sorted(&KEY_A.1));
+  assert!(confidence <= start);
+  assert_eq!(
+    confidence,
+    get_confidence.run(&CONTEXT.decipher(&ciphertext, &key))
+  );
+}
+
+fn swapped(
+  square: &PolybiusSquare,
+  swaps: &[(usize, usize)],
+) -> PolybiusSquare {
+  let mut letters = square.letters().to_vec();
+  swaps.iter().for_each(|&(a, b)| letters.swap(a, b));
+  PolybiusSquare::from_letters(letters, square.merge()).unwrap()
+}
+
+fn sorted(square: &PolybiusSquare) -> Vec<char> {
+  let mut letters = square.letters().to_vec();
+  letters.sort_unstable();
+  letters
+}
+
+cipher_test!(CONTEXT, *HELP_ME, KEY_A, help_me);
@@ -0,0 +1,46 @@
+//! Helpers shared by the fractionating ciphers, which split every letter
+//! into its coordinates in a square or cube and recombine them across a
+//! block of letters.
+
+/// Writes the coordinates of every block of `period` letters out one
+/// dimension after another and reads them back `D` at a time. A period of
+/// zero treats the whole text as one block.
+pub(crate) fn fractionate<const D: usize>(
+  letters: &[[usize; D]],
+  period: usize,
+) -> Vec<[usize; D]> {
+  blocks(letters, period)
+    .flat_map(|block| {
+      let coordinates: Vec<usize> = (0..D)
+        .flat_map(|d| block.iter().map(move |c| c[d]))
+        .collect();
+      coordinates
+        .chunks(D)
+        .map(|chunk| std::array::from_fn(|d| chunk[d]))
+        .collect::<Vec<_>>()
+    })
+    .collect()
+}
+
+/// Undoes `fractionate`.
+pub(crate) fn unfractionate<const D: usize>(
+  letters: &[[usize; D]],
+  period: usize,
+) -> Vec<[usize; D]> {
+  blocks(letters, period)
+    .flat_map(|block| {
+      let coordinates: Vec<usize> = block.iter().flatten().copied().collect();
+      (0..block.len())
+        .map(|k| std::array::from_fn(|d| coordinates[d * block.len() + k]))
+        .collect::<Vec<_>>()
+    })
+    .collect()
+}
+
+fn blocks<T>(letters: &[T], period: usize) -> std::slice::Chunks<'_, T> {
+  letters.chunks(if period == 0 {
+    letters.len().max(1)
+  } else {
+    period
+  })
+}
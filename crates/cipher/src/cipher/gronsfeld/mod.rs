@@ -0,0 +1,127 @@
+use crate::{
+  alphabet::Alphabet,
+  cipher::{Decipher, Encipher},
+};
+use cipher_derive::{IntoDecipherKey, IntoEncipherKey};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use stutter_zip::StutterZipIterator;
+
+/// A Vigenère key written as digits, each the shift for one position.
+#[derive(
+  Debug, Clone, Serialize, Deserialize, IntoDecipherKey, IntoEncipherKey,
+)]
+pub struct GronsfeldKey(Vec<usize>);
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Gronsfeld {
+  alphabet: Alphabet,
+  skip_whitespace: bool,
+}
+
+#[derive(Debug)]
+pub enum ParseError {
+  InvalidChar(char),
+}
+
+impl fmt::Display for ParseError {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    match self {
+      ParseError::InvalidChar(c) => write!(f, "Invalid character: '{c}'"),
+    }
+  }
+}
+
+impl GronsfeldKey {
+  pub fn len(&self) -> usize {
+    self.0.len()
+  }
+
+  pub fn is_empty(&self) -> bool {
+    self.0.is_empty()
+  }
+
+  pub fn try_new(key: &str, context: &Gronsfeld) -> Result<Self, ParseError> {
+    let shifts = key
+      .chars()
+      .map(|c| match c.to_digit(10) {
+        Some(d) if (d as usize) < context.alphabet.len() => Ok(d as usize),
+        _ => Err(ParseError::InvalidChar(c)),
+      })
+      .collect::<Result<_, _>>()?;
+
+    Ok(GronsfeldKey::new(shifts))
+  }
+
+  pub fn new(shifts: Vec<usize>) -> Self {
+    GronsfeldKey(shifts)
+  }
+}
+
+impl TryFrom<(&str, &Gronsfeld)> for GronsfeldKey {
+  type Error = ParseError;
+
+  fn try_from((key, context): (&str, &Gronsfeld)) -> Result<Self, Self::Error> {
+    Self::try_new(key, context)
+  }
+}
+
+impl std::fmt::Display for GronsfeldKey {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    self.0.iter().try_for_each(|d| write!(f, "{d}"))
+  }
+}
+
+impl Gronsfeld {
+  pub fn new(alphabet: Alphabet, skip_whitespace: bool) -> Self {
+    Gronsfeld {
+      alphabet,
+      skip_whitespace,
+    }
+  }
+
+  fn shift(&self, text: &str, key: &GronsfeldKey, encipher: bool) -> String {
+    if key.is_empty() {
+      return text.to_string();
+    }
+
+    let len = self.alphabet.len();
+    text
+      .chars()
+      .stutter_zip(key.0.iter().cycle(), |c| {
+        c.is_whitespace() && self.skip_whitespace
+      })
+      .map(|(c, d)| {
+        d.zip(self.alphabet.get_index(c))
+          .and_then(|(d, i)| {
+            let d = d % len;
+            self.alphabet.get(if encipher {
+              (i + d) % len
+            } else {
+              (i + len - d) % len
+            })
+          })
+          .unwrap_or(c)
+      })
+      .collect()
+  }
+}
+
+impl Encipher for Gronsfeld {
+  type Key = GronsfeldKey;
+
+  fn encipher(&self, plaintext: &str, key: &Self::Key) -> String {
+    self.shift(plaintext, key, true)
+  }
+}
+
+impl Decipher for Gronsfeld {
+  type Key = GronsfeldKey;
+
+  fn decipher(&self, ciphertext: &str, key: &Self::Key) -> String {
+    self.shift(ciphertext, key, false)
+  }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,37 @@
+use super::*;
+use crate::alphabet::Alphabet;
+use crate::cipher_test;
+use crate::tests::*;
+use once_cell::sync::Lazy;
+
+pub static CONTEXT_INCLUDE_WHITESPACE: Lazy<Gronsfeld> =
+  Lazy::new(|| Gronsfeld::new(Alphabet::latin(), false));
+pub static CONTEXT_SKIP_WHITESPACE: Lazy<Gronsfeld> =
+  Lazy::new(|| Gronsfeld::new(Alphabet::latin(), true));
+
+pub static KEY_A: Lazy<GronsfeldKey> =
+  Lazy::new(|| GronsfeldKey::new(vec![3, 1, 4, 1, 5]));
+
+#[test]
+fn key_try_new() {
+  let key = GronsfeldKey::try_new("31415", &CONTEXT_INCLUDE_WHITESPACE);
+  assert_eq!(key.map(|k| k.to_string()).ok(), Some("31415".to_string()));
+  assert!(matches!(
+    GronsfeldKey::try_new("3x4", &CONTEXT_INCLUDE_WHITESPACE),
+    Err(ParseError::InvalidChar('x')),
+  ));
+}
+
+cipher_test!(
+  CONTEXT_INCLUDE_WHITESPACE,
+  *QUICK_BROWN_FOX,
+  KEY_A,
+  include_whitespace
+);
+
+cipher_test!(
+  CONTEXT_SKIP_WHITESPACE,
+  *QUICK_BROWN_FOX,
+  KEY_A,
+  skip_whitespace
+);
@@ -0,0 +1,80 @@
+//! Square matrices of letter indices, stored row by row, with arithmetic
+//! modulo the alphabet length. Determinants use cofactor expansion, which
+//! is plenty for the small keys the Hill cipher takes.
+
+use crate::cipher::modular::modular_inverse;
+
+pub(super) fn determinant(matrix: &[usize], size: usize, m: usize) -> usize {
+  if size == 0 {
+    return 1 % m;
+  }
+
+  (0..size).fold(0, |determinant, column| {
+    let term = matrix[column] * cofactor(matrix, size, 0, column, m) % m;
+    (determinant + term) % m
+  })
+}
+
+/// The inverse of `matrix`, if its determinant is coprime with `m`.
+pub(super) fn inverse(
+  matrix: &[usize],
+  size: usize,
+  m: usize,
+) -> Option<Vec<usize>> {
+  let determinant_inverse = modular_inverse(determinant(matrix, size, m), m)?;
+
+  // The adjugate is the transposed matrix of cofactors.
+  let inverse = (0..size * size)
+    .map(|i| {
+      let (row, column) = (i / size, i % size);
+      cofactor(matrix, size, column, row, m) * determinant_inverse % m
+    })
+    .collect();
+
+  Some(inverse)
+}
+
+/// The product of a `size`x`size` matrix and a matrix with `size` rows.
+pub(super) fn multiply(
+  a: &[usize],
+  b: &[usize],
+  size: usize,
+  m: usize,
+) -> Vec<usize> {
+  let columns = b.len() / size;
+
+  (0..size * columns)
+    .map(|i| {
+      let (row, column) = (i / columns, i % columns);
+      (0..size)
+        .map(|k| a[row * size + k] * b[k * columns + column])
+        .sum::<usize>()
+        % m
+    })
+    .collect()
+}
+
+/// The signed determinant of `matrix` without `row` and `column`.
+fn cofactor(
+  matrix: &[usize],
+  size: usize,
+  row: usize,
+  column: usize,
+  m: usize,
+) -> usize {
+  let minor: Vec<usize> = (0..size)
+    .filter(|&r| r != row)
+    .flat_map(|r| {
+      (0..size)
+        .filter(move |&c| c != column)
+        .map(move |c| matrix[r * size + c])
+    })
+    .collect();
+  let determinant = determinant(&minor, size - 1, m);
+
+  if (row + column).is_multiple_of(2) {
+    determinant
+  } else {
+    (m - determinant) % m
+  }
+}
@@ -0,0 +1,215 @@
+mod matrix;
+mod solve;
+
+use crate::{
+  alphabet::Alphabet,
+  cipher::{Decipher, Encipher, KeysIterator},
+};
+use cipher_derive::{IntoDecipherKey, IntoEncipherKey};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// A square matrix, stored row by row, that multiplies every block of
+/// letter indices, together with its inverse for deciphering.
+#[derive(
+  Debug, Clone, Serialize, Deserialize, IntoDecipherKey, IntoEncipherKey,
+)]
+pub struct HillKey {
+  size: usize,
+  matrix: Vec<usize>,
+  inverse: Vec<usize>,
+}
+
+/// Treats every block of letters as a vector of alphabet indices and
+/// multiplies it by the key matrix modulo the alphabet length. Letters not
+/// in the alphabet are dropped, and the last block is filled up with
+/// `padding`, which must be in the alphabet and which deciphering keeps.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Hill {
+  alphabet: Alphabet,
+  padding: char,
+}
+
+#[derive(Debug)]
+pub enum ParseError {
+  InvalidNumber(String),
+  InvalidChar(char),
+  NotSquare(usize),
+  NotInvertible(usize, usize),
+}
+
+impl fmt::Display for ParseError {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    match self {
+      ParseError::InvalidNumber(n) => write!(f, "Invalid number: '{n}'"),
+      ParseError::InvalidChar(c) => write!(f, "Invalid character: '{c}'"),
+      ParseError::NotSquare(len) => {
+        write!(f, "{len} entries do not fill a square matrix")
+      }
+      ParseError::NotInvertible(determinant, len) => write!(
+        f,
+        "Determinant {determinant} is not coprime with the alphabet length \
+         {len}, so the matrix has no inverse"
+      ),
+    }
+  }
+}
+
+impl HillKey {
+  /// Parses the matrix row by row, either as comma-separated numbers or as
+  /// a keyword with one letter per entry.
+  pub fn try_new(key: &str, context: &Hill) -> Result<Self, ParseError> {
+    let len = context.alphabet.len();
+    // A single number is the 1x1 matrix.
+    let number = key.trim();
+    let numeric = key.contains(',')
+      || (!number.is_empty() && number.chars().all(|c| c.is_ascii_digit()));
+    let entries = if numeric {
+      key
+        .split(',')
+        .map(|n| match n.trim().parse::<usize>() {
+          Ok(n) if n < len => Ok(n),
+          _ => Err(ParseError::InvalidNumber(n.to_string())),
+        })
+        .collect::<Result<_, _>>()?
+    } else {
+      key
+        .chars()
+        .map(|c| {
+          context
+            .alphabet
+            .get_index(c)
+            .ok_or(ParseError::InvalidChar(c))
+        })
+        .collect::<Result<_, _>>()?
+    };
+
+    Self::from_matrix(entries, len)
+  }
+
+  /// Builds a key from its entries row by row, modulo `len`.
+  pub fn from_matrix(
+    matrix: Vec<usize>,
+    len: usize,
+  ) -> Result<Self, ParseError> {
+    let size = (matrix.len() as f64).sqrt().round() as usize;
+    if size == 0 || size * size != matrix.len() {
+      return Err(ParseError::NotSquare(matrix.len()));
+    }
+
+    let matrix: Vec<usize> = matrix.into_iter().map(|n| n % len).collect();
+    let inverse = matrix::inverse(&matrix, size, len).ok_or_else(|| {
+      ParseError::NotInvertible(matrix::determinant(&matrix, size, len), len)
+    })?;
+
+    Ok(HillKey {
+      size,
+      matrix,
+      inverse,
+    })
+  }
+
+  pub fn size(&self) -> usize {
+    self.size
+  }
+
+  pub fn matrix(&self) -> &[usize] {
+    &self.matrix
+  }
+}
+
+impl TryFrom<(&str, &Hill)> for HillKey {
+  type Error = ParseError;
+
+  fn try_from((key, context): (&str, &Hill)) -> Result<Self, Self::Error> {
+    Self::try_new(key, context)
+  }
+}
+
+impl fmt::Display for HillKey {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    let entries: Vec<String> =
+      self.matrix.iter().map(|n| n.to_string()).collect();
+    write!(f, "{}", entries.join(","))
+  }
+}
+
+impl Hill {
+  pub fn new(alphabet: Alphabet, padding: char) -> Result<Self, ParseError> {
+    if !alphabet.contains(padding) {
+      return Err(ParseError::InvalidChar(padding));
+    }
+
+    Ok(Hill { alphabet, padding })
+  }
+
+  fn indices(&self, text: &str) -> Vec<usize> {
+    text
+      .chars()
+      .filter_map(|c| self.alphabet.get_index(c))
+      .collect()
+  }
+
+  /// Multiplies every complete block of `letters` by `matrix`. An
+  /// incomplete last block is left as it is.
+  fn transform(
+    &self,
+    letters: &[usize],
+    matrix: &[usize],
+    size: usize,
+  ) -> String {
+    let len = self.alphabet.len();
+    let blocks = letters.chunks_exact(size);
+    let remainder = blocks.remainder();
+
+    blocks
+      .flat_map(|block| matrix::multiply(matrix, block, size, len))
+      .chain(remainder.iter().copied())
+      .filter_map(|i| self.alphabet.get(i))
+      .collect()
+  }
+}
+
+impl Encipher for Hill {
+  type Key = HillKey;
+
+  fn encipher(&self, plaintext: &str, key: &Self::Key) -> String {
+    let mut letters = self.indices(plaintext);
+    // Hill::new only accepts a padding letter from the alphabet.
+    let padding = self.alphabet.get_index(self.padding).unwrap_or(0);
+    while !letters.len().is_multiple_of(key.size) {
+      letters.push(padding);
+    }
+
+    self.transform(&letters, &key.matrix, key.size)
+  }
+}
+
+impl Decipher for Hill {
+  type Key = HillKey;
+
+  fn decipher(&self, ciphertext: &str, key: &Self::Key) -> String {
+    self.transform(&self.indices(ciphertext), &key.inverse, key.size)
+  }
+}
+
+/// Every invertible 2x2 key; larger keys are too many to try.
+impl KeysIterator for Hill {
+  type KeysIter = <std::vec::Vec<HillKey> as std::iter::IntoIterator>::IntoIter;
+
+  fn keys_iter(&self) -> Self::KeysIter {
+    let len = self.alphabet.len();
+    let keys: Vec<HillKey> = (0..len.pow(4))
+      .filter_map(|i| {
+        let matrix =
+          vec![i / len.pow(3), i / len.pow(2) % len, i / len % len, i % len];
+        HillKey::from_matrix(matrix, len).ok()
+      })
+      .collect();
+
+    keys.into_iter()
+  }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,37 @@
+use super::{Hill, HillKey, matrix};
+use itertools::Itertools;
+
+impl Hill {
+  /// Recovers a key of `size` from a known plaintext and its ciphertext,
+  /// both starting at a block boundary. Any `size` blocks whose plaintext
+  /// matrix is invertible fix the key, since the ciphertext blocks are the
+  /// key times the plaintext blocks; returns `None` when no such blocks
+  /// exist, including when the texts have fewer than `size` blocks.
+  pub fn known_plaintext_key(
+    &self,
+    plaintext: &str,
+    ciphertext: &str,
+    size: usize,
+  ) -> Option<HillKey> {
+    let len = self.alphabet.len();
+    let plaintext = self.indices(plaintext);
+    let ciphertext = self.indices(ciphertext);
+    let blocks = plaintext.len().min(ciphertext.len()).checked_div(size)?;
+    if blocks < size {
+      return None;
+    }
+
+    (0..blocks).combinations(size).find_map(|chosen| {
+      // The chosen blocks as the columns of a matrix.
+      let columns = |text: &[usize]| -> Vec<usize> {
+        (0..size * size)
+          .map(|i| text[chosen[i % size] * size + i / size])
+          .collect()
+      };
+      let inverse = matrix::inverse(&columns(&plaintext), size, len)?;
+      let key = matrix::multiply(&columns(&ciphertext), &inverse, size, len);
+
+      HillKey::from_matrix(key, len).ok()
+    })
+  }
+}
@@ -0,0 +1,105 @@
+use super::*;
+use crate::alphabet::Alphabet;
+use crate::cipher_test;
+use crate::tests::*;
+use once_cell::sync::Lazy;
+
+pub static CONTEXT: Lazy<Hill> =
+  Lazy::new(|| Hill::new(Alphabet::latin(), 'x').unwrap());
+
+pub static KEY_HILL: Lazy<HillKey> =
+  Lazy::new(|| HillKey::try_new("hill", &CONTEXT).unwrap());
+pub static KEY_3X3: Lazy<HillKey> =
+  Lazy::new(|| HillKey::try_new("gybnqkurp", &CONTEXT).unwrap());
+
+// A whole number of blocks for both keys, so no padding is added.
+static ATTACK_AT_DAWN: Lazy<String> = Lazy::new(|| "attackatdawn".to_string());
+
+// The tale without the characters the cipher drops.
+static TALE: Lazy<String> = Lazy::new(|| {
+  A_TALE_OF_TWO_CITIES
+    .chars()
+    .filter(|c| c.is_alphabetic())
+    .collect()
+});
+
+#[test]
+fn key_try_new() {
+  assert_eq!(KEY_HILL.matrix(), [7, 8, 11, 11]);
+  assert_eq!(
+    HillKey::try_new("3,3,2,5", &CONTEXT).unwrap().to_string(),
+    "3,3,2,5"
+  );
+  assert!(matches!(
+    HillKey::try_new("abc", &CONTEXT),
+    Err(ParseError::NotSquare(3))
+  ));
+  assert!(matches!(
+    HillKey::try_new("3,3,2,26", &CONTEXT),
+    Err(ParseError::InvalidNumber(_))
+  ));
+  assert!(matches!(
+    Hill::new(Alphabet::latin(), 'X'),
+    Err(ParseError::InvalidChar('X'))
+  ));
+  assert!(matches!(
+    HillKey::try_new("h1ll", &CONTEXT),
+    Err(ParseError::InvalidChar('1'))
+  ));
+  assert_eq!(HillKey::try_new("5", &CONTEXT).unwrap().matrix(), [5]);
+  assert!(matches!(
+    HillKey::try_new("3,3,x,5", &CONTEXT),
+    Err(ParseError::InvalidNumber(_))
+  ));
+  // Determinant 2 shares a factor with 26.
+  assert!(matches!(
+    HillKey::try_new("2,0,0,1", &CONTEXT),
+    Err(ParseError::NotInvertible(2, 26))
+  ));
+}
+
+// Both keys are Wikipedia's examples.
+#[test]
+fn examples() {
+  assert_eq!(CONTEXT.encipher("short example", &KEY_HILL), "apadjtftwlfj");
+  assert_eq!(CONTEXT.encipher("act", &KEY_3X3), "poh");
+  assert_eq!(CONTEXT.decipher("poh", &KEY_3X3), "act");
+}
+
+#[test]
+fn padding() {
+  let ciphertext = CONTEXT.encipher("abcd", &KEY_3X3);
+
+  assert_eq!(ciphertext.len(), 6);
+  assert_eq!(CONTEXT.decipher(&ciphertext, &KEY_3X3), "abcdxx");
+}
+
+#[test]
+fn keys_iter() {
+  // Invertible 2x2 matrices modulo 26.
+  assert_eq!(CONTEXT.keys_iter().count(), 157_248);
+}
+
+#[test]
+fn known_plaintext_key() {
+  let plaintext: String = TALE.chars().take(60).collect();
+
+  for key in [&*KEY_HILL, &*KEY_3X3] {
+    let ciphertext = CONTEXT.encipher(&plaintext, key);
+    let recovered = CONTEXT
+      .known_plaintext_key(&plaintext, &ciphertext, key.size())
+      .unwrap();
+
+    assert_eq!(recovered.matrix(), key.matrix());
+  }
+  assert!(CONTEXT.known_plaintext_key("abcd", "efgh", 0).is_none());
+  assert!(CONTEXT.known_plaintext_key("abcd", "efgh", 3).is_none());
+  assert!(
+    CONTEXT
+      .known_plaintext_key("abcd", "efgh", usize::MAX)
+      .is_none()
+  );
+}
+
+cipher_test!(CONTEXT, *ATTACK_AT_DAWN, KEY_HILL, attack_at_dawn_2x2);
+cipher_test!(CONTEXT, *ATTACK_AT_DAWN, KEY_3X3, attack_at_dawn_3x3);
@@ -0,0 +1,225 @@
+mod solve;
+
+use crate::{
+  alphabet::Alphabet,
+  cipher::{Decipher, Encipher},
+};
+use ahash::AHashMap;
+use cipher_derive::{IntoDecipherKey, IntoEncipherKey};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// The cipher symbols standing for every letter. Letters may have any
+/// number of symbols, including none, but a symbol stands for one letter.
+#[derive(
+  Debug, Clone, Serialize, Deserialize, IntoDecipherKey, IntoEncipherKey,
+)]
+pub struct HomophonicKey {
+  homophones: Vec<(char, Vec<String>)>,
+  symbols: AHashMap<String, char>,
+}
+
+/// Replaces every letter with one of its cipher symbols, taking them in
+/// turn so that common letters are spread over several symbols. Symbols
+/// are written `width` characters long with nothing between them, or
+/// separated by spaces when there is no width. Letters without symbols,
+/// like those outside the alphabet, are dropped; unknown symbols decipher
+/// to '�'.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Homophonic {
+  alphabet: Alphabet,
+  width: Option<usize>,
+}
+
+#[derive(Debug)]
+pub enum ParseError {
+  InvalidFormat(String),
+  InvalidChar(char),
+  DuplicateLetter(char),
+  DuplicateSymbol(String),
+  InvalidSymbol(String),
+}
+
+impl fmt::Display for ParseError {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    match self {
+      ParseError::InvalidFormat(part) => write!(
+        f,
+        "Invalid homophones: '{part}', expected a letter, '=' and its \
+         symbols separated by spaces, e.g. 'e=12 47 81'"
+      ),
+      ParseError::InvalidChar(c) => write!(f, "Invalid character: '{c}'"),
+      ParseError::DuplicateLetter(c) => {
+        write!(f, "Letter '{c}' is given more than once")
+      }
+      ParseError::DuplicateSymbol(symbol) => {
+        write!(f, "Symbol '{symbol}' stands for more than one letter")
+      }
+      ParseError::InvalidSymbol(symbol) => {
+        write!(f, "Symbol '{symbol}' does not have the cipher's width")
+      }
+    }
+  }
+}
+
+impl HomophonicKey {
+  /// Parses the homophones of every letter, separated by commas, as the
+  /// letter, '=' and its symbols separated by spaces:
+  /// `a=01 27,b=02,c=03 28 ...`.
+  pub fn try_new(key: &str, context: &Homophonic) -> Result<Self, ParseError> {
+    let homophones = key
+      .split(',')
+      .filter(|part| !part.trim().is_empty())
+      .map(|part| {
+        let (letter, symbols) = part
+          .split_once('=')
+          .ok_or_else(|| ParseError::InvalidFormat(part.to_string()))?;
+        let letter = match letter.trim().chars().collect::<Vec<_>>()[..] {
+          [c] => c,
+          _ => return Err(ParseError::InvalidFormat(part.to_string())),
+        };
+        let symbols =
+          symbols.split_whitespace().map(|s| s.to_string()).collect();
+
+        Ok((letter, symbols))
+      })
+      .collect::<Result<_, _>>()?;
+
+    Self::new(homophones, context)
+  }
+
+  pub fn new(
+    mut homophones: Vec<(char, Vec<String>)>,
+    context: &Homophonic,
+  ) -> Result<Self, ParseError> {
+    let mut symbols = AHashMap::new();
+    for (i, (letter, letter_symbols)) in homophones.iter().enumerate() {
+      if !context.alphabet.contains(*letter) {
+        return Err(ParseError::InvalidChar(*letter));
+      }
+      if homophones[..i].iter().any(|(c, _)| c == letter) {
+        return Err(ParseError::DuplicateLetter(*letter));
+      }
+      for symbol in letter_symbols {
+        if context
+          .width
+          .is_some_and(|width| symbol.chars().count() != width)
+        {
+          return Err(ParseError::InvalidSymbol(symbol.clone()));
+        }
+        if symbols.insert(symbol.clone(), *letter).is_some() {
+          return Err(ParseError::DuplicateSymbol(symbol.clone()));
+        }
+      }
+    }
+
+    homophones.sort_by_key(|(letter, _)| context.alphabet.get_index(*letter));
+
+    Ok(HomophonicKey {
+      homophones,
+      symbols,
+    })
+  }
+
+  fn homophones(&self, letter: char) -> Option<&[String]> {
+    self
+      .homophones
+      .iter()
+      .find(|(c, _)| *c == letter)
+      .map(|(_, symbols)| symbols.as_slice())
+  }
+}
+
+impl TryFrom<(&str, &Homophonic)> for HomophonicKey {
+  type Error = ParseError;
+
+  fn try_from(
+    (key, context): (&str, &Homophonic),
+  ) -> Result<Self, Self::Error> {
+    Self::try_new(key, context)
+  }
+}
+
+impl fmt::Display for HomophonicKey {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    let homophones: Vec<String> = self
+      .homophones
+      .iter()
+      .filter(|(_, symbols)| !symbols.is_empty())
+      .map(|(letter, symbols)| format!("{letter}={}", symbols.join(" ")))
+      .collect();
+
+    write!(f, "{}", homophones.join(","))
+  }
+}
+
+impl Homophonic {
+  pub fn new(alphabet: Alphabet, width: Option<usize>) -> Self {
+    Homophonic { alphabet, width }
+  }
+
+  pub fn alphabet(&self) -> &Alphabet {
+    &self.alphabet
+  }
+
+  /// Splits `ciphertext` into its symbols. Whitespace only separates
+  /// symbols, so fixed-width symbols may be grouped freely.
+  pub fn symbols(&self, ciphertext: &str) -> Vec<String> {
+    match self.width {
+      Some(width) => {
+        let chars: Vec<char> =
+          ciphertext.chars().filter(|c| !c.is_whitespace()).collect();
+        chars
+          .chunks(width.max(1))
+          .map(|symbol| symbol.iter().collect())
+          .collect()
+      }
+      None => ciphertext
+        .split_whitespace()
+        .map(|symbol| symbol.to_string())
+        .collect(),
+    }
+  }
+
+  fn join(&self, symbols: &[&str]) -> String {
+    match self.width {
+      Some(_) => symbols.concat(),
+      None => symbols.join(" "),
+    }
+  }
+}
+
+impl Encipher for Homophonic {
+  type Key = HomophonicKey;
+
+  fn encipher(&self, plaintext: &str, key: &Self::Key) -> String {
+    let mut used: AHashMap<char, usize> = AHashMap::new();
+    let symbols: Vec<&str> = plaintext
+      .chars()
+      .filter_map(|c| {
+        let homophones = key.homophones(c).filter(|h| !h.is_empty())?;
+        let used = used.entry(c).or_insert(0);
+        let symbol = &homophones[*used % homophones.len()];
+        *used += 1;
+        Some(symbol.as_str())
+      })
+      .collect();
+
+    self.join(&symbols)
+  }
+}
+
+impl Decipher for Homophonic {
+  type Key = HomophonicKey;
+
+  fn decipher(&self, ciphertext: &str, key: &Self::Key) -> String {
+    self
+      .symbols(ciphertext)
+      .iter()
+      .map(|symbol| key.symbols.get(symbol).copied().unwrap_or('�'))
+      .collect()
+  }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,192 @@
+use super::{Homophonic, HomophonicKey};
+use crate::language::{GetConfidence, Language};
+use crate::periodicity;
+use crate::search::{self, AnnealOptions};
+use ahash::AHashMap;
+use rand::{Rng, RngExt};
+
+impl Homophonic {
+  /// Gives every symbol of `ciphertext` a random letter.
+  pub fn random_key<R: Rng>(
+    &self,
+    ciphertext: &str,
+    rng: &mut R,
+  ) -> HomophonicKey {
+    let (symbols, _) = self.distinct_symbols(ciphertext);
+    let letters: Vec<_> = symbols
+      .iter()
+      .map(|_| rng.random_range(0..self.alphabet.len()))
+      .collect();
+
+    self.key(&symbols, &letters)
+  }
+
+  /// Gives the symbols of `ciphertext`, most frequent first, to the letter
+  /// of `language` that is furthest below its expected count, so common
+  /// letters end up with several symbols.
+  pub fn frequency_key(
+    &self,
+    ciphertext: &str,
+    language: &Language,
+  ) -> HomophonicKey {
+    let (symbols, text) = self.distinct_symbols(ciphertext);
+    let mut counts = vec![0usize; symbols.len()];
+    for &i in &text {
+      counts[i] += 1;
+    }
+    let mut order: Vec<_> = (0..symbols.len()).collect();
+    order.sort_by_key(|&i| std::cmp::Reverse(counts[i]));
+
+    let frequencies: Vec<f64> = self
+      .alphabet
+      .iter()
+      .map(|c| {
+        language
+          .char_ngrams(1)
+          .map_or(0.0, |unigrams| unigrams.get(&c.to_string()))
+      })
+      .collect();
+    let mut deficits: Vec<f64> =
+      frequencies.iter().map(|f| f * text.len() as f64).collect();
+
+    let mut letters = vec![0; symbols.len()];
+    for i in order {
+      let letter = (0..deficits.len())
+        .max_by(|&a, &b| deficits[a].total_cmp(&deficits[b]))
+        .unwrap_or(0);
+      letters[i] = letter;
+      deficits[letter] -= counts[i] as f64;
+    }
+
+    self.key(&symbols, &letters)
+  }
+
+  /// Improves `key` by giving a symbol another letter, or by swapping the
+  /// letters of two symbols, keeping changes that lower the confidence
+  /// score of the deciphered text. Symbols of `ciphertext` missing from
+  /// `key` start on the first letter of the alphabet.
+  ///
+  /// Nothing stops a homophonic key from giving most symbols to a few
+  /// common letters, which n-gram scores favour, so the search also counts
+  /// how far the index of coincidence of the plaintext rises above that of
+  /// `language`, relative to the gap between the language and random text.
+  /// A rise as large as that gap costs one unit of score per letter, which
+  /// suits log n-gram scores; a language no closer than random text to
+  /// repeating letters gets no such penalty. The confidence returned is that
+  /// of the plaintext alone.
+  pub fn hill_climb<R: Rng>(
+    &self,
+    ciphertext: &str,
+    key: HomophonicKey,
+    get_confidence: &GetConfidence,
+    language: &Language,
+    options: &AnnealOptions,
+    rng: &mut R,
+  ) -> (HomophonicKey, f64) {
+    let (symbols, text) = self.distinct_symbols(ciphertext);
+    if symbols.is_empty() {
+      return (key, get_confidence.run(""));
+    }
+
+    let language_ioc = language.index_of_coincidence();
+    let gap = language_ioc - 1.0 / self.alphabet.len() as f64;
+    let plaintext = |letters: &[usize]| -> String {
+      text
+        .iter()
+        .filter_map(|&i| self.alphabet.get(letters[i]))
+        .collect()
+    };
+    let score = |letters: &[usize]| {
+      let ioc = periodicity::index_of_coincidence(
+        text.iter().map(|&i| letters[i]),
+        self.alphabet.len(),
+      );
+      let excess = if gap > 0.0 {
+        (ioc - language_ioc).max(0.0) / gap
+      } else {
+        0.0
+      };
+
+      get_confidence.run(&plaintext(letters)) + excess
+    };
+
+    let letters: Vec<usize> = symbols
+      .iter()
+      .map(|symbol| {
+        key
+          .symbols
+          .get(symbol)
+          .and_then(|&c| self.alphabet.get_index(c))
+          .unwrap_or(0)
+      })
+      .collect();
+
+    let (letters, _) = search::anneal(
+      letters,
+      options,
+      rng,
+      |letters, rng| {
+        let mut letters = letters.clone();
+        let i = rng.random_range(0..letters.len());
+        if rng.random_bool(0.5) {
+          letters[i] = rng.random_range(0..self.alphabet.len());
+        } else {
+          let j = rng.random_range(0..letters.len());
+          letters.swap(i, j);
+        }
+        letters
+      },
+      |letters| score(letters),
+    );
+    let confidence = get_confidence.run(&plaintext(&letters));
+
+    (self.key(&symbols, &letters), confidence)
+  }
+
+  /// The distinct symbols of `ciphertext`, in order of first appearance,
+  /// and the ciphertext as indices into them.
+  fn distinct_symbols(&self, ciphertext: &str) -> (Vec<String>, Vec<usize>) {
+    let mut indices: AHashMap<String, usize> = AHashMap::new();
+    let mut symbols = Vec::new();
+    let text = self
+      .symbols(ciphertext)
+      .into_iter()
+      .map(|symbol| {
+        *indices.entry(symbol.clone()).or_insert_with(|| {
+          symbols.push(symbol);
+          symbols.len() - 1
+        })
+      })
+      .collect();
+
+    (symbols, text)
+  }
+
+  /// The key giving every symbol the letter at the same index.
+  fn key(&self, symbols: &[String], letters: &[usize]) -> HomophonicKey {
+    let homophones = self
+      .alphabet
+      .iter()
+      .enumerate()
+      .map(|(letter, c)| {
+        let letter_symbols = symbols
+          .iter()
+          .zip(letters)
+          .filter(|(_, l)| **l == letter)
+          .map(|(symbol, _)| symbol.clone())
+          .collect();
+        (c, letter_symbols)
+      })
+      .collect();
+    let symbols = symbols
+      .iter()
+      .zip(letters)
+      .filter_map(|(symbol, &l)| Some((symbol.clone(), self.alphabet.get(l)?)))
+      .collect();
+
+    HomophonicKey {
+      homophones,
+      symbols,
+    }
+  }
+}
@@ -0,0 +1,176 @@
+use super::*;
+use crate::alphabet::Alphabet;
+use crate::cipher_test;
+use crate::tests::*;
+use once_cell::sync::Lazy;
+
+pub static CONTEXT: Lazy<Homophonic> =
+  Lazy::new(|| Homophonic::new(Alphabet::latin(), Some(2)));
+
+pub static KEY_A: Lazy<HomophonicKey> = Lazy::new(|| {
+  HomophonicKey::try_new(
+    "a=01 27,b=02,c=03,d=04 28,e=05 29 45,f=06,g=07,h=08 30,i=09 31,j=10,\
+     k=11,l=12 32,m=13,n=14 33,o=15 34,p=16,q=17,r=18 35,s=19 36,\
+     t=20 37 46,u=21,v=22,w=23,x=24,y=25,z=26",
+    &CONTEXT,
+  )
+  .unwrap()
+});
+
+static TALE: Lazy<String> = Lazy::new(|| {
+  A_TALE_OF_TWO_CITIES
+    .chars()
+    .filter(|c| c.is_ascii_lowercase())
+    .collect()
+});
+
+// The fox without its trailing newline, which has no symbol.
+static FOX: Lazy<String> = Lazy::new(|| QUICKBROWNFOX.trim().to_string());
+
+#[test]
+fn key_try_new() {
+  assert!(matches!(
+    HomophonicKey::try_new("a=01,b", &CONTEXT),
+    Err(ParseError::InvalidFormat(_)),
+  ));
+  assert!(matches!(
+    HomophonicKey::try_new("a=01,7=02", &CONTEXT),
+    Err(ParseError::InvalidChar('7')),
+  ));
+  assert!(matches!(
+    HomophonicKey::try_new("a=01,a=02", &CONTEXT),
+    Err(ParseError::DuplicateLetter('a')),
+  ));
+  assert!(matches!(
+    HomophonicKey::try_new("a=01,b=02 01", &CONTEXT),
+    Err(ParseError::DuplicateSymbol(symbol)) if symbol == "01",
+  ));
+  assert!(matches!(
+    HomophonicKey::try_new("a=01,b=2", &CONTEXT),
+    Err(ParseError::InvalidSymbol(symbol)) if symbol == "2",
+  ));
+}
+
+#[test]
+fn key_display() {
+  let key = HomophonicKey::try_new("e=05 29,a=01", &CONTEXT).unwrap();
+
+  assert_eq!(key.to_string(), "a=01,e=05 29");
+}
+
+#[test]
+fn homophones_in_turn() {
+  assert_eq!(CONTEXT.encipher("eeee", &KEY_A), "05294505");
+
+  let context = Homophonic::new(Alphabet::latin(), None);
+  let key = HomophonicKey::try_new("e=five twenty-nine,t=t", &context).unwrap();
+  assert_eq!(context.encipher("tee", &key), "t five twenty-nine");
+  assert_eq!(context.decipher("t twenty-nine nine", &key), "te�");
+}
+
+#[test]
+fn grouped_symbols() {
+  assert_eq!(CONTEXT.decipher("0529 4505", &KEY_A), "eeee");
+}
+
+cipher_test!(CONTEXT, *FOX, KEY_A, width_2);
+
+#[test]
+fn frequency_key() {
+  use crate::language::Language;
+  use crate::ngrams::RankedNgrams;
+
+  let unigrams = RankedNgrams::from_iter(
+    [("e", 3), ("t", 1)]
+      .into_iter()
+      .map(|(ngram, count)| (ngram.to_string(), count as u64)),
+  );
+  let language = Language::new(
+    RankedNgrams::default(),
+    AHashMap::from([(1, unigrams)]),
+    0.0,
+  );
+
+  // Two symbols for e and two for t.
+  let key = CONTEXT.frequency_key("1213121312131415", &language);
+  assert_eq!(CONTEXT.decipher("12131415", &key), "eett");
+}
+
+#[test]
+fn hill_climb() {
+  use crate::language::GetConfidence;
+  use crate::search::AnnealOptions;
+  use rand::{SeedableRng, rngs::StdRng};
+  use std::sync::Arc;
+
+  let ciphertext = CONTEXT.encipher(&TALE, &KEY_A);
+  let table = ENGLISH.log_ngram_table(3).unwrap();
+  let get_confidence =
+    GetConfidence::new(Arc::new(move |text: &str| table.text_confidence(text)));
+  let options = AnnealOptions {
+    iterations: 50000,
+    temperature: 0.02,
+  };
+
+  let mut rng = StdRng::seed_from_u64(0);
+  let solved = (0..5).any(|_| {
+    let key = CONTEXT.frequency_key(&ciphertext, &ENGLISH);
+    let (key, _) = CONTEXT.hill_climb(
+      &ciphertext,
+      key,
+      &get_confidence,
+      &ENGLISH,
+      &options,
+      &mut rng,
+    );
+    CONTEXT.decipher(&ciphertext, &key) == *TALE
+  });
+
+  assert!(solved);
+}
+
+#[test]
+fn hill_climb_without_ioc_gap() {
+  use crate::language::{GetConfidence, Language};
+  use crate::ngrams::RankedNgrams;
+  use crate::search::AnnealOptions;
+  use ahash::AHashMap;
+  use rand::{SeedableRng, rngs::StdRng};
+  use std::sync::Arc;
+
+  // A language no likelier than random text to repeat letters, as a
+  // language file may claim.
+  let language = Language::new(
+    RankedNgrams::from_iter(Vec::<(String, u64)>::new()),
+    AHashMap::new(),
+    1.0 / 26.0,
+  );
+  let ciphertext = CONTEXT.encipher(&TALE, &KEY_A);
+  let table = ENGLISH.log_ngram_table(3).unwrap();
+  let get_confidence =
+    GetConfidence::new(Arc::new(move |text: &str| table.text_confidence(text)));
+  let options = AnnealOptions {
+    iterations: 200,
+    temperature: 0.0,
+  };
+
+  let mut rng = StdRng::seed_from_u64(0);
+  let key = CONTEXT.random_key(&ciphertext, &mut rng);
+  let start = get_confidence.run(&CONTEXT.decipher(&ciphertext, &key));
+  let (key, confidence) = CONTEXT.hill_climb(
+    &ciphertext,
+    key,
+    &get_confidence,
+    &language,
+    &options,
+    &mut rng,
+  );
+
+  // Dividing by the missing gap would make every score NaN and stop the
+  // search from ever moving.
+  assert!(confidence < start);
+  assert_eq!(
+    confidence,
+    get_confidence.run(&CONTEXT.decipher(&ciphertext, &key))
+  );
+}
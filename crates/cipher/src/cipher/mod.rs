@@ -1,8 +1,31 @@
+pub mod adfgx;
+pub mod affine;
 pub mod autokey;
+pub mod beaufort;
+pub mod bifid;
 pub mod caesar;
+pub mod columnar;
+pub mod enigma;
+pub mod four_square;
+pub mod gronsfeld;
+pub mod hill;
+pub mod homophonic;
+pub mod playfair;
+pub mod porta;
+pub mod quagmire;
+pub mod rail_fence;
+pub mod route;
+pub mod running_key;
 pub mod substitution;
+pub mod trifid;
+pub mod two_square;
 pub mod vigenere;
 
+mod digraphic;
+mod fractionation;
+mod modular;
+mod transposition;
+
 pub trait Encipher {
   type Key;
 
@@ -0,0 +1,11 @@
+//! Arithmetic modulo the length of an alphabet, shared by the ciphers that
+//! treat letters as numbers.
+
+pub(crate) fn gcd(a: usize, b: usize) -> usize {
+  if b == 0 { a } else { gcd(b, a % b) }
+}
+
+/// The `x` with `a * x = 1 (mod m)`, if `a` and `m` are coprime.
+pub(crate) fn modular_inverse(a: usize, m: usize) -> Option<usize> {
+  (1..m).find(|x| (a * x) % m == 1)
+}
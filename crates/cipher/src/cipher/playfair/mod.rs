@@ -0,0 +1,182 @@
+mod solve;
+
+use crate::{
+  alphabet::Alphabet,
+  cipher::{Decipher, Encipher},
+  polybius::{ParseError, PolybiusSquare},
+};
+use cipher_derive::{IntoDecipherKey, IntoEncipherKey};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+#[derive(
+  Debug, Clone, Serialize, Deserialize, IntoDecipherKey, IntoEncipherKey,
+)]
+pub struct PlayfairKey(PolybiusSquare);
+
+/// Enciphers pairs of letters with a keyed Polybius square. Letters not in
+/// the square, whitespace included, are dropped; repeated letters within a
+/// pair are split with `filler`, which also pads an odd final letter. A
+/// filler in the plaintext is split or padded with the letter after it in
+/// the alphabet instead. Deciphering keeps the fillers.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Playfair {
+  alphabet: Alphabet,
+  merge: Option<(char, char)>,
+  filler: char,
+}
+
+impl PlayfairKey {
+  pub fn try_new(
+    keyword: &str,
+    context: &Playfair,
+  ) -> Result<Self, ParseError> {
+    PolybiusSquare::new(keyword, &context.alphabet, context.merge)
+      .map(PlayfairKey::new)
+  }
+
+  pub fn new(square: PolybiusSquare) -> Self {
+    PlayfairKey(square)
+  }
+
+  pub fn square(&self) -> &PolybiusSquare {
+    &self.0
+  }
+}
+
+impl TryFrom<(&str, &Playfair)> for PlayfairKey {
+  type Error = ParseError;
+
+  fn try_from((key, context): (&str, &Playfair)) -> Result<Self, Self::Error> {
+    Self::try_new(key, context)
+  }
+}
+
+impl fmt::Display for PlayfairKey {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(f, "{}", self.0)
+  }
+}
+
+impl Playfair {
+  pub fn new(
+    alphabet: Alphabet,
+    merge: Option<(char, char)>,
+    filler: char,
+  ) -> Self {
+    Playfair {
+      alphabet,
+      merge,
+      filler,
+    }
+  }
+
+  /// Splits `plaintext` into the pairs that get enciphered.
+  fn digraphs(&self, plaintext: &str, square: &PolybiusSquare) -> Vec<char> {
+    let mut letters = plaintext
+      .chars()
+      .filter(|&c| square.contains(c))
+      .map(|c| square.normalize(c))
+      .peekable();
+    let filler = square.normalize(self.filler);
+    let mut digraphs = Vec::new();
+
+    while let Some(a) = letters.next() {
+      digraphs.push(a);
+      match letters.peek() {
+        Some(&b) if b != a => {
+          digraphs.push(b);
+          letters.next();
+        }
+        _ if a == filler => digraphs.push(self.second_filler(square)),
+        _ => digraphs.push(filler),
+      }
+    }
+
+    digraphs
+  }
+
+  /// The letter after `filler` in the alphabet, which splits or pads a
+  /// filler, since a pair of fillers would encipher as a doubled letter.
+  fn second_filler(&self, square: &PolybiusSquare) -> char {
+    let filler = square.normalize(self.filler);
+    let len = self.alphabet.len();
+    self
+      .alphabet
+      .get_index(self.filler)
+      .and_then(|i| {
+        (1..len)
+          .filter_map(|k| self.alphabet.get((i + k) % len))
+          .map(|c| square.normalize(c))
+          .find(|&c| c != filler && square.contains(c))
+      })
+      .unwrap_or(filler)
+  }
+
+  /// Moves every pair across the square, `step` places along rows and
+  /// columns for pairs sharing one of them.
+  fn substitute(
+    &self,
+    letters: &[char],
+    square: &PolybiusSquare,
+    step: usize,
+  ) -> String {
+    let side = square.side();
+    letters
+      .chunks(2)
+      .flat_map(|pair| {
+        let (Some(&a), Some(&b)) = (pair.first(), pair.get(1)) else {
+          return pair.to_vec();
+        };
+        let (Some((ar, ac)), Some((br, bc))) =
+          (square.position(a), square.position(b))
+        else {
+          return pair.to_vec();
+        };
+
+        let (a, b) = if ar == br {
+          (
+            square.get(ar, (ac + step) % side),
+            square.get(br, (bc + step) % side),
+          )
+        } else if ac == bc {
+          (
+            square.get((ar + step) % side, ac),
+            square.get((br + step) % side, bc),
+          )
+        } else {
+          (square.get(ar, bc), square.get(br, ac))
+        };
+
+        vec![a.unwrap_or(pair[0]), b.unwrap_or(pair[1])]
+      })
+      .collect()
+  }
+}
+
+impl Encipher for Playfair {
+  type Key = PlayfairKey;
+
+  fn encipher(&self, plaintext: &str, key: &Self::Key) -> String {
+    let digraphs = self.digraphs(plaintext, &key.0);
+
+    self.substitute(&digraphs, &key.0, 1)
+  }
+}
+
+impl Decipher for Playfair {
+  type Key = PlayfairKey;
+
+  fn decipher(&self, ciphertext: &str, key: &Self::Key) -> String {
+    let letters: Vec<char> = ciphertext
+      .chars()
+      .filter(|&c| key.0.contains(c))
+      .map(|c| key.0.normalize(c))
+      .collect();
+
+    self.substitute(&letters, &key.0, key.0.side() - 1)
+  }
+}
+
+#[cfg(test)]
+mod tests;
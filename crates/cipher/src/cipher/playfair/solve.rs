@@ -0,0 +1,75 @@
+use super::{Playfair, PlayfairKey};
+use crate::cipher::Decipher;
+use crate::language::GetConfidence;
+use crate::polybius::PolybiusSquare;
+use crate::search::{self, AnnealOptions};
+use rand::{Rng, RngExt, seq::SliceRandom};
+
+impl Playfair {
+  pub fn random_key<R: Rng>(&self, rng: &mut R) -> PlayfairKey {
+    let mut letters = self.square_letters();
+    letters.shuffle(rng);
+
+    self.key_from_letters(letters)
+  }
+
+  /// Improves `key` by swapping letters, and now and then whole rows or
+  /// columns, of its square, keeping changes that lower the confidence
+  /// score of the deciphered text. Playfair has many local optima, so a
+  /// positive annealing temperature works much better than hill climbing.
+  pub fn hill_climb<R: Rng>(
+    &self,
+    ciphertext: &str,
+    key: PlayfairKey,
+    get_confidence: &GetConfidence,
+    options: &AnnealOptions,
+    rng: &mut R,
+  ) -> (PlayfairKey, f64) {
+    let side = key.0.side();
+    if side < 2 {
+      let confidence = get_confidence.run(&self.decipher(ciphertext, &key));
+      return (key, confidence);
+    }
+
+    let (letters, confidence) = search::anneal(
+      key.0.letters().to_vec(),
+      options,
+      rng,
+      |letters, rng| {
+        let mut letters = letters.clone();
+        let i = rng.random_range(0..side);
+        let j = rng.random_range(0..side);
+        match rng.random_range(0..10) {
+          0 => (0..side).for_each(|k| letters.swap(i * side + k, j * side + k)),
+          1 => (0..side).for_each(|k| letters.swap(k * side + i, k * side + j)),
+          _ => {
+            let a = rng.random_range(0..letters.len());
+            let b = rng.random_range(0..letters.len());
+            letters.swap(a, b);
+          }
+        }
+        letters
+      },
+      |letters| {
+        let key = self.key_from_letters(letters.clone());
+        get_confidence.run(&self.decipher(ciphertext, &key))
+      },
+    );
+
+    (self.key_from_letters(letters), confidence)
+  }
+
+  fn square_letters(&self) -> Vec<char> {
+    PolybiusSquare::new("", &self.alphabet, self.merge)
+      .map(|square| square.letters().to_vec())
+      .unwrap_or_default()
+  }
+
+  fn key_from_letters(&self, letters: Vec<char>) -> PlayfairKey {
+    // Rearranging the letters of a valid square always gives a square.
+    PlayfairKey::new(
+      PolybiusSquare::from_letters(letters, self.merge)
+        .expect("letters of a square fill a square"),
+    )
+  }
+}
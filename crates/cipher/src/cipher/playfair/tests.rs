@@ -0,0 +1,110 @@
+use super::*;
+use crate::alphabet::Alphabet;
+use crate::cipher_test;
+use crate::language::GetConfidence;
+use crate::search::AnnealOptions;
+use crate::tests::*;
+use once_cell::sync::Lazy;
+use std::sync::Arc;
+
+pub static CONTEXT: Lazy<Playfair> =
+  Lazy::new(|| Playfair::new(Alphabet::latin(), Some(('j', 'i')), 'x'));
+
+pub static KEY_A: Lazy<PlayfairKey> =
+  Lazy::new(|| PlayfairKey::try_new("playfairexample", &CONTEXT).unwrap());
+
+// Already split into digraphs, so deciphering gives it back unchanged.
+static HIDE_THE_GOLD: Lazy<String> =
+  Lazy::new(|| "hidethegoldinthetrexestump".to_string());
+
+#[test]
+fn key_try_new() {
+  assert_eq!(KEY_A.to_string(), "playfirexmbcdghknoqstuvwz");
+  assert!(matches!(
+    PlayfairKey::try_new("gr8ness", &CONTEXT),
+    Err(ParseError::InvalidChar('8')),
+  ));
+
+  let russian = Playfair::new(Alphabet::russian(), None, 'х');
+  assert!(matches!(
+    PlayfairKey::try_new("", &russian),
+    Err(ParseError::NotSquare(33)),
+  ));
+  assert!(matches!(
+    PolybiusSquare::from_letters("abca".chars().collect(), None),
+    Err(ParseError::RepeatedChar('a')),
+  ));
+}
+
+#[test]
+fn wikipedia_example() {
+  assert_eq!(
+    CONTEXT.encipher("hide the gold in the tree stump", &KEY_A),
+    "bmodzbxdnabekudmuixmmouvif"
+  );
+}
+
+#[test]
+fn digraph_rules() {
+  // Doubled letters are split, J is merged into I and an odd final letter
+  // is padded.
+  let ciphertext = CONTEXT.encipher("jelly", &KEY_A);
+
+  assert_eq!(CONTEXT.decipher(&ciphertext, &KEY_A), "ielxly");
+
+  // A filler is split and padded with the letter after it.
+  let ciphertext = CONTEXT.encipher("xx", &KEY_A);
+
+  assert_eq!(CONTEXT.decipher(&ciphertext, &KEY_A), "xyxy");
+}
+
+#[test]
+fn hill_climb() {
+  use rand::{SeedableRng, rngs::StdRng};
+
+  let ciphertext = CONTEXT.encipher(&A_TALE_OF_TWO_CITIES, &KEY_A);
+  let plaintext = CONTEXT.decipher(&ciphertext, &KEY_A);
+  let mut letters = KEY_A.square().letters().to_vec();
+  letters.swap(0, 7);
+  letters.swap(3, 18);
+  let key = PlayfairKey::new(
+    PolybiusSquare::from_letters(letters, Some(('j', 'i'))).unwrap(),
+  );
+  let options = AnnealOptions {
+    iterations: 2000,
+    temperature: 0.0,
+  };
+
+  let mut rng = StdRng::seed_from_u64(0);
+  let (key, _) =
+    CONTEXT.hill_climb(&ciphertext, key, &get_confidence(), &options, &mut rng);
+
+  assert_eq!(CONTEXT.decipher(&ciphertext, &key), plaintext);
+}
+
+#[test]
+#[ignore = "anneals from a random key for minutes in a debug build"]
+fn hill_climb_random_key() {
+  use rand::{SeedableRng, rngs::StdRng};
+
+  let ciphertext = CONTEXT.encipher(&A_TALE_OF_TWO_CITIES, &KEY_A);
+  let plaintext = CONTEXT.decipher(&ciphertext, &KEY_A);
+  let options = AnnealOptions {
+    iterations: 400000,
+    temperature: 0.1,
+  };
+
+  let mut rng = StdRng::seed_from_u64(0);
+  let key = CONTEXT.random_key(&mut rng);
+  let (key, _) =
+    CONTEXT.hill_climb(&ciphertext, key, &get_confidence(), &options, &mut rng);
+
+  assert_eq!(CONTEXT.decipher(&ciphertext, &key), plaintext);
+}
+
+fn get_confidence() -> GetConfidence {
+  let table = ENGLISH.log_ngram_table(3).unwrap();
+  GetConfidence::new(Arc::new(move |text: &str| table.text_confidence(text)))
+}
+
+cipher_test!(CONTEXT, *HIDE_THE_GOLD, KEY_A, hide_the_gold);
@@ -0,0 +1,131 @@
+use crate::{
+  alphabet::Alphabet,
+  cipher::{Decipher, Encipher},
+};
+use cipher_derive::{IntoDecipherKey, IntoEncipherKey};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use stutter_zip::StutterZipIterator;
+
+#[derive(
+  Debug, Clone, Serialize, Deserialize, IntoDecipherKey, IntoEncipherKey,
+)]
+pub struct PortaKey(String);
+
+/// The alphabet is split into two halves and every pair of key letters
+/// selects a shift that swaps letters between them, so enciphering and
+/// deciphering are the same operation. For Latin this is the classic
+/// 13-alphabet tableau; the last letter of an odd-length alphabet is left
+/// unchanged.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Porta {
+  alphabet: Alphabet,
+  skip_whitespace: bool,
+}
+
+#[derive(Debug)]
+pub enum ParseError {
+  InvalidChar(char),
+}
+
+impl fmt::Display for ParseError {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    match self {
+      ParseError::InvalidChar(c) => write!(f, "Invalid character: '{c}'"),
+    }
+  }
+}
+
+impl PortaKey {
+  pub fn len(&self) -> usize {
+    self.0.len()
+  }
+
+  pub fn is_empty(&self) -> bool {
+    self.0.is_empty()
+  }
+
+  pub fn try_new(key: String, context: &Porta) -> Result<Self, ParseError> {
+    if let Some(k) = key.chars().find(|&k| !context.alphabet.contains(k)) {
+      return Err(ParseError::InvalidChar(k));
+    }
+
+    Ok(PortaKey::new(key))
+  }
+
+  pub fn new(key: String) -> Self {
+    PortaKey(key)
+  }
+}
+
+impl TryFrom<(&str, &Porta)> for PortaKey {
+  type Error = ParseError;
+
+  fn try_from((key, context): (&str, &Porta)) -> Result<Self, Self::Error> {
+    Self::try_new(key.to_string(), context)
+  }
+}
+
+impl std::fmt::Display for PortaKey {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    write!(f, "{}", self.0)
+  }
+}
+
+impl Porta {
+  pub fn new(alphabet: Alphabet, skip_whitespace: bool) -> Self {
+    Porta {
+      alphabet,
+      skip_whitespace,
+    }
+  }
+
+  fn swap(&self, c: char, k: char) -> char {
+    let half = self.alphabet.len() / 2;
+    let (Some(i), Some(k)) =
+      (self.alphabet.get_index(c), self.alphabet.get_index(k))
+    else {
+      return c;
+    };
+    let shift = (k / 2) % half;
+
+    let j = if i < half {
+      half + (i + shift) % half
+    } else if i < 2 * half {
+      (i - half + half - shift) % half
+    } else {
+      i
+    };
+
+    self.alphabet.get(j).unwrap_or(c)
+  }
+}
+
+impl Encipher for Porta {
+  type Key = PortaKey;
+
+  fn encipher(&self, plaintext: &str, key: &Self::Key) -> String {
+    if key.is_empty() {
+      return plaintext.to_string();
+    }
+
+    plaintext
+      .chars()
+      .stutter_zip(key.0.chars().cycle(), |p| {
+        p.is_whitespace() && self.skip_whitespace
+      })
+      .map(|(p, k)| k.map(|k| self.swap(p, k)).unwrap_or(p))
+      .collect()
+  }
+}
+
+impl Decipher for Porta {
+  type Key = PortaKey;
+
+  fn decipher(&self, ciphertext: &str, key: &Self::Key) -> String {
+    self.encipher(ciphertext, key)
+  }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,65 @@
+use super::*;
+use crate::alphabet::Alphabet;
+use crate::cipher_test;
+use crate::tests::*;
+use once_cell::sync::Lazy;
+
+pub static CONTEXT_INCLUDE_WHITESPACE: Lazy<Porta> =
+  Lazy::new(|| Porta::new(Alphabet::latin(), false));
+pub static CONTEXT_SKIP_WHITESPACE: Lazy<Porta> =
+  Lazy::new(|| Porta::new(Alphabet::latin(), true));
+pub static CONTEXT_RUSSIAN: Lazy<Porta> =
+  Lazy::new(|| Porta::new(Alphabet::russian(), true));
+
+pub static KEY_A: Lazy<PortaKey> =
+  Lazy::new(|| PortaKey::new("key".to_string()));
+
+#[test]
+fn key_try_new() {
+  assert!(
+    PortaKey::try_new("hello".to_string(), &CONTEXT_INCLUDE_WHITESPACE).is_ok()
+  );
+  assert!(matches!(
+    PortaKey::try_new("gr8ness".to_string(), &CONTEXT_INCLUDE_WHITESPACE),
+    Err(ParseError::InvalidChar('8')),
+  ));
+}
+
+#[test]
+fn tableau() {
+  let a = PortaKey::new("a".to_string());
+  let d = PortaKey::new("d".to_string());
+
+  assert_eq!(
+    CONTEXT_SKIP_WHITESPACE.encipher("abcdefghijklmnopqrstuvwxyz", &a),
+    "nopqrstuvwxyzabcdefghijklm"
+  );
+  assert_eq!(
+    CONTEXT_SKIP_WHITESPACE.encipher("abcdefghijklmnopqrstuvwxyz", &d),
+    "opqrstuvwxyznmabcdefghijkl"
+  );
+}
+
+#[test]
+fn russian_round_trip() {
+  let key = PortaKey::new("ключ".to_string());
+  let plaintext = "съешь же ещё этих мягких французских булок";
+  let ciphertext = CONTEXT_RUSSIAN.encipher(plaintext, &key);
+
+  assert_ne!(ciphertext, plaintext);
+  assert_eq!(CONTEXT_RUSSIAN.decipher(&ciphertext, &key), plaintext);
+}
+
+cipher_test!(
+  CONTEXT_INCLUDE_WHITESPACE,
+  *QUICK_BROWN_FOX,
+  KEY_A,
+  include_whitespace
+);
+
+cipher_test!(
+  CONTEXT_SKIP_WHITESPACE,
+  *QUICK_BROWN_FOX,
+  KEY_A,
+  skip_whitespace
+);
@@ -0,0 +1,234 @@
+use crate::{
+  alphabet::{self, Alphabet},
+  cipher::{Decipher, Encipher},
+};
+use cipher_derive::{IntoDecipherKey, IntoEncipherKey};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use stutter_zip::StutterZipIterator;
+
+/// The keywords mixing the plain and cipher alphabets, and the indicator,
+/// whose letters select the shift of the cipher alphabet in turn.
+#[derive(
+  Debug, Clone, Serialize, Deserialize, IntoDecipherKey, IntoEncipherKey,
+)]
+pub struct QuagmireKey {
+  keywords: Vec<String>,
+  plain: Alphabet,
+  cipher: Alphabet,
+  indicator: String,
+}
+
+/// Which of the plain and cipher alphabets are mixed with a keyword.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Variant {
+  /// Mixed plain alphabet, straight cipher alphabet.
+  I,
+  /// Straight plain alphabet, mixed cipher alphabet.
+  II,
+  /// The same mixed alphabet for both.
+  III,
+  /// Plain and cipher alphabets mixed with different keywords.
+  IV,
+}
+
+/// The Quagmire periodic ciphers: Vigenère with keyword-mixed alphabets.
+/// For every letter of the indicator in turn, the cipher alphabet slides
+/// under the plain alphabet until that letter sits under the plain
+/// alphabet's anchor, and the plaintext letter is replaced with the one
+/// below it. As in the ACA's conventions, the anchor is the first letter of
+/// the alphabet for Quagmire I and II, and the first letter of the plain
+/// keyword for Quagmire III and IV. That letter starts the mixed plain
+/// alphabet, unless the alphabet is read off by columns.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Quagmire {
+  alphabet: Alphabet,
+  variant: Variant,
+  columnar: bool,
+  skip_whitespace: bool,
+}
+
+#[derive(Debug)]
+pub enum ParseError {
+  InvalidFormat(Variant),
+  InvalidChar(char),
+}
+
+impl fmt::Display for ParseError {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    match self {
+      ParseError::InvalidFormat(Variant::IV) => write!(
+        f,
+        "Expected 'plain keyword,cipher keyword,indicator', e.g. \
+         'springfever,kryptos,flower'"
+      ),
+      ParseError::InvalidFormat(_) => {
+        write!(f, "Expected 'keyword,indicator', e.g. 'springfever,flower'")
+      }
+      ParseError::InvalidChar(c) => write!(f, "Invalid character: '{c}'"),
+    }
+  }
+}
+
+impl From<alphabet::ParseError> for ParseError {
+  fn from(value: alphabet::ParseError) -> Self {
+    match value {
+      alphabet::ParseError::InvalidChar(c) => ParseError::InvalidChar(c),
+    }
+  }
+}
+
+impl QuagmireKey {
+  /// Parses `keyword,indicator`, or `plain keyword,cipher keyword,indicator`
+  /// for Quagmire IV.
+  pub fn try_new(key: &str, context: &Quagmire) -> Result<Self, ParseError> {
+    let parts: Vec<&str> = key.split(',').map(str::trim).collect();
+    let (keywords, indicator) = match (context.variant, &parts[..]) {
+      (Variant::IV, [plain, cipher, indicator]) => {
+        (vec![plain.to_string(), cipher.to_string()], indicator)
+      }
+      (Variant::I | Variant::II | Variant::III, [keyword, indicator]) => {
+        (vec![keyword.to_string()], indicator)
+      }
+      _ => return Err(ParseError::InvalidFormat(context.variant)),
+    };
+
+    Self::new(keywords, indicator.to_string(), context)
+  }
+
+  pub fn new(
+    keywords: Vec<String>,
+    indicator: String,
+    context: &Quagmire,
+  ) -> Result<Self, ParseError> {
+    if let Some(c) = indicator.chars().find(|&c| !context.alphabet.contains(c))
+    {
+      return Err(ParseError::InvalidChar(c));
+    }
+
+    let mixed =
+      |keyword: &str| context.alphabet.keyword_mixed(keyword, context.columnar);
+    let straight = context.alphabet.clone();
+    let (plain, cipher) = match (context.variant, &keywords[..]) {
+      (Variant::I, [keyword]) => (mixed(keyword)?, straight),
+      (Variant::II, [keyword]) => (straight, mixed(keyword)?),
+      (Variant::III, [keyword]) => (mixed(keyword)?, mixed(keyword)?),
+      (Variant::IV, [plain, cipher]) => (mixed(plain)?, mixed(cipher)?),
+      _ => return Err(ParseError::InvalidFormat(context.variant)),
+    };
+
+    Ok(QuagmireKey {
+      keywords,
+      plain,
+      cipher,
+      indicator,
+    })
+  }
+
+  pub fn is_empty(&self) -> bool {
+    self.indicator.is_empty()
+  }
+}
+
+impl TryFrom<(&str, &Quagmire)> for QuagmireKey {
+  type Error = ParseError;
+
+  fn try_from((key, context): (&str, &Quagmire)) -> Result<Self, Self::Error> {
+    Self::try_new(key, context)
+  }
+}
+
+impl fmt::Display for QuagmireKey {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(f, "{},{}", self.keywords.join(","), self.indicator)
+  }
+}
+
+impl Quagmire {
+  pub fn new(
+    alphabet: Alphabet,
+    variant: Variant,
+    columnar: bool,
+    skip_whitespace: bool,
+  ) -> Self {
+    Quagmire {
+      alphabet,
+      variant,
+      columnar,
+      skip_whitespace,
+    }
+  }
+
+  /// Position of the cipher alphabet for indicator letter `k`: the index in
+  /// the cipher alphabet of the letter under the start of the plain one.
+  fn shift(&self, key: &QuagmireKey, k: char) -> Option<usize> {
+    let anchor = match self.variant {
+      Variant::I | Variant::II => key.plain.get_index(self.alphabet.get(0)?)?,
+      // An empty keyword leaves the plain alphabet straight.
+      Variant::III | Variant::IV => key
+        .keywords
+        .first()
+        .and_then(|keyword| keyword.chars().next())
+        .and_then(|c| key.plain.get_index(c))
+        .unwrap_or(0),
+    };
+    let k = key.cipher.get_index(k)?;
+
+    Some((self.alphabet.len() + k - anchor) % self.alphabet.len())
+  }
+}
+
+impl Encipher for Quagmire {
+  type Key = QuagmireKey;
+
+  fn encipher(&self, plaintext: &str, key: &Self::Key) -> String {
+    if key.is_empty() {
+      return plaintext.to_string();
+    }
+
+    plaintext
+      .chars()
+      .stutter_zip(key.indicator.chars().cycle(), |p| {
+        p.is_whitespace() && self.skip_whitespace
+      })
+      .map(|(p, k)| {
+        k.and_then(|k| {
+          let i = key.plain.get_index(p)?;
+          let shift = self.shift(key, k)?;
+          key.cipher.get((i + shift) % self.alphabet.len())
+        })
+        .unwrap_or(p)
+      })
+      .collect()
+  }
+}
+
+impl Decipher for Quagmire {
+  type Key = QuagmireKey;
+
+  fn decipher(&self, ciphertext: &str, key: &Self::Key) -> String {
+    if key.is_empty() {
+      return ciphertext.to_string();
+    }
+
+    ciphertext
+      .chars()
+      .stutter_zip(key.indicator.chars().cycle(), |c| {
+        c.is_whitespace() && self.skip_whitespace
+      })
+      .map(|(c, k)| {
+        k.and_then(|k| {
+          let i = key.cipher.get_index(c)?;
+          let shift = self.shift(key, k)?;
+          key
+            .plain
+            .get((self.alphabet.len() + i - shift) % self.alphabet.len())
+        })
+        .unwrap_or(c)
+      })
+      .collect()
+  }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,104 @@
+use super::*;
+use crate::alphabet::Alphabet;
+use crate::cipher_test;
+use crate::tests::*;
+use once_cell::sync::Lazy;
+
+fn context(variant: Variant) -> Quagmire {
+  Quagmire::new(Alphabet::latin(), variant, false, true)
+}
+
+fn key(key: &str, variant: Variant) -> QuagmireKey {
+  QuagmireKey::try_new(key, &context(variant)).unwrap()
+}
+
+static CONTEXT_I: Lazy<Quagmire> = Lazy::new(|| context(Variant::I));
+static CONTEXT_II: Lazy<Quagmire> = Lazy::new(|| context(Variant::II));
+static CONTEXT_III: Lazy<Quagmire> = Lazy::new(|| context(Variant::III));
+static CONTEXT_IV: Lazy<Quagmire> = Lazy::new(|| context(Variant::IV));
+
+static KEY_I: Lazy<QuagmireKey> =
+  Lazy::new(|| key("springfever,flower", Variant::I));
+static KEY_II: Lazy<QuagmireKey> =
+  Lazy::new(|| key("springfever,flower", Variant::II));
+static KEY_III: Lazy<QuagmireKey> =
+  Lazy::new(|| key("springfever,flower", Variant::III));
+static KEY_IV: Lazy<QuagmireKey> =
+  Lazy::new(|| key("springfever,kryptos,flower", Variant::IV));
+
+#[test]
+fn key_try_new() {
+  assert!(matches!(
+    QuagmireKey::try_new("springfever", &CONTEXT_I),
+    Err(ParseError::InvalidFormat(Variant::I)),
+  ));
+  assert!(matches!(
+    QuagmireKey::try_new("springfever,flower", &CONTEXT_IV),
+    Err(ParseError::InvalidFormat(Variant::IV)),
+  ));
+  assert!(matches!(
+    QuagmireKey::try_new("spring fever,flower", &CONTEXT_II),
+    Err(ParseError::InvalidChar(' ')),
+  ));
+  assert!(matches!(
+    QuagmireKey::try_new("springfever,fl0wer", &CONTEXT_III),
+    Err(ParseError::InvalidChar('0')),
+  ));
+  assert_eq!(KEY_IV.to_string(), "springfever,kryptos,flower");
+}
+
+#[test]
+fn indicator_under_anchor() {
+  // Under the first letter of the alphabet for Quagmire I and II, and of
+  // the mixed plain alphabet for III and IV.
+  for (context, key, anchor) in [
+    (&CONTEXT_I, &KEY_I, "aaaaaa"),
+    (&CONTEXT_II, &KEY_II, "aaaaaa"),
+    (&CONTEXT_III, &KEY_III, "ssssss"),
+    (&CONTEXT_IV, &KEY_IV, "ssssss"),
+  ] {
+    assert_eq!(context.encipher(anchor, key), "flower");
+  }
+}
+
+#[test]
+fn columnar_anchor() {
+  // Read off by columns, the mixed plain alphabet starts with the column
+  // under E rather than with the keyword, whose first letter stays the
+  // anchor.
+  let context = Quagmire::new(Alphabet::latin(), Variant::III, true, true);
+  let key = QuagmireKey::try_new("springfever,flower", &context).unwrap();
+  assert_eq!(key.plain.iter().take(3).collect::<String>(), "elz");
+
+  assert_eq!(context.encipher("ssssss", &key), "flower");
+  assert_eq!(context.decipher("flower", &key), "ssssss");
+}
+
+#[test]
+fn kryptos_k1() {
+  let key = key("kryptos,palimpsest", Variant::III);
+  let ciphertext =
+    "EMUFPHZLRFAXYUSDJKZLDKRNSHGNFIVJYQTQUXQBQVYUVLLTREVJYQTMKYRDMFD";
+
+  assert_eq!(
+    CONTEXT_III.decipher(&ciphertext.to_lowercase(), &key),
+    "betweensubtleshadingandtheabsenceoflightliesthenuanceofiqlusion"
+  );
+}
+
+#[test]
+fn mixed_alphabets() {
+  // The plain alphabet starts "springfevabc", so with indicator 'a' it
+  // lies over a straight alphabet starting nine letters before 'a'.
+  let key = key("springfever,a", Variant::I);
+  assert_eq!(CONTEXT_I.encipher("springfevabc", &key), "rstuvwxyzabc");
+
+  // Quagmire II reads the mixed cipher alphabet from the indicator.
+  let key = self::key("springfever,e", Variant::II);
+  assert_eq!(CONTEXT_II.encipher("abcd", &key), "evab");
+}
+
+cipher_test!(CONTEXT_I, *QUICK_BROWN_FOX, KEY_I, quagmire_i);
+cipher_test!(CONTEXT_II, *QUICK_BROWN_FOX, KEY_II, quagmire_ii);
+cipher_test!(CONTEXT_III, *QUICK_BROWN_FOX, KEY_III, quagmire_iii);
+cipher_test!(CONTEXT_IV, *QUICK_BROWN_FOX, KEY_IV, quagmire_iv);
@@ -0,0 +1,152 @@
+use crate::cipher::{Decipher, Encipher, KeysIterator, transposition};
+use cipher_derive::{IntoDecipherKey, IntoEncipherKey};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// Writes the text in a zigzag over `rails` rows and reads the rows top to
+/// bottom. The zigzag starts `offset` steps into its cycle.
+#[derive(
+  Debug, Clone, Serialize, Deserialize, IntoDecipherKey, IntoEncipherKey,
+)]
+pub struct RailFenceKey {
+  rails: usize,
+  offset: usize,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RailFence {
+  skip_whitespace: bool,
+  max_rails: usize,
+}
+
+#[derive(Debug)]
+pub enum ParseError {
+  InvalidFormat(String),
+  InvalidRails(usize),
+  InvalidOffset(usize),
+}
+
+impl fmt::Display for ParseError {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    match self {
+      ParseError::InvalidFormat(key) => {
+        write!(
+          f,
+          "Invalid key: '{key}', expected 'rails' or 'rails,offset'"
+        )
+      }
+      ParseError::InvalidRails(rails) => {
+        write!(f, "Invalid number of rails: {rails}")
+      }
+      ParseError::InvalidOffset(offset) => {
+        write!(f, "Offset {offset} is longer than the zigzag cycle")
+      }
+    }
+  }
+}
+
+impl RailFenceKey {
+  pub fn try_new(rails: usize, offset: usize) -> Result<Self, ParseError> {
+    if rails < 2 {
+      return Err(ParseError::InvalidRails(rails));
+    }
+    if offset >= cycle(rails) {
+      return Err(ParseError::InvalidOffset(offset));
+    }
+
+    Ok(Self::new(rails, offset))
+  }
+
+  pub(crate) fn new(rails: usize, offset: usize) -> Self {
+    RailFenceKey { rails, offset }
+  }
+}
+
+impl TryFrom<(&str, &RailFence)> for RailFenceKey {
+  type Error = ParseError;
+
+  fn try_from((key, _): (&str, &RailFence)) -> Result<Self, Self::Error> {
+    let invalid = || ParseError::InvalidFormat(key.to_string());
+    let (rails, offset) = key.split_once(',').unwrap_or((key, "0"));
+    let rails = rails.trim().parse().map_err(|_| invalid())?;
+    let offset = offset.trim().parse().map_err(|_| invalid())?;
+
+    Self::try_new(rails, offset)
+  }
+}
+
+impl fmt::Display for RailFenceKey {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(f, "{},{}", self.rails, self.offset)
+  }
+}
+
+impl RailFence {
+  /// `max_rails` bounds the keys tried by `keys_iter`.
+  pub fn new(skip_whitespace: bool, max_rails: usize) -> Self {
+    RailFence {
+      skip_whitespace,
+      max_rails,
+    }
+  }
+}
+
+fn cycle(rails: usize) -> usize {
+  2 * (rails - 1)
+}
+
+/// Positions of the written text in the order they are read out.
+fn read_positions(len: usize, key: &RailFenceKey) -> Vec<usize> {
+  let cycle = cycle(key.rails);
+  let rail = |i: usize| {
+    let step = (i + key.offset) % cycle;
+    step.min(cycle - step)
+  };
+
+  let mut positions: Vec<_> = (0..len).collect();
+  positions.sort_by_key(|&i| rail(i));
+
+  positions
+}
+
+impl Encipher for RailFence {
+  type Key = RailFenceKey;
+
+  fn encipher(&self, plaintext: &str, key: &Self::Key) -> String {
+    let letters = transposition::letters(plaintext, self.skip_whitespace);
+    let positions = read_positions(letters.len(), key);
+    let ciphertext = transposition::read(&letters, &positions);
+
+    transposition::reinsert(plaintext, self.skip_whitespace, ciphertext)
+  }
+}
+
+impl Decipher for RailFence {
+  type Key = RailFenceKey;
+
+  fn decipher(&self, ciphertext: &str, key: &Self::Key) -> String {
+    let letters = transposition::letters(ciphertext, self.skip_whitespace);
+    let positions = read_positions(letters.len(), key);
+    let plaintext = transposition::write(&letters, &positions);
+
+    transposition::reinsert(ciphertext, self.skip_whitespace, plaintext)
+  }
+}
+
+impl KeysIterator for RailFence {
+  type KeysIter =
+    <std::vec::Vec<RailFenceKey> as std::iter::IntoIterator>::IntoIter;
+
+  fn keys_iter(&self) -> Self::KeysIter {
+    let keys: Vec<RailFenceKey> = (2..=self.max_rails)
+      .flat_map(|rails| {
+        (0..cycle(rails)).map(move |offset| RailFenceKey::new(rails, offset))
+      })
+      .collect();
+
+    keys.into_iter()
+  }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,64 @@
+use super::*;
+use crate::cipher_test;
+use crate::tests::*;
+use once_cell::sync::Lazy;
+
+pub static CONTEXT_INCLUDE_WHITESPACE: Lazy<RailFence> =
+  Lazy::new(|| RailFence::new(false, 10));
+pub static CONTEXT_SKIP_WHITESPACE: Lazy<RailFence> =
+  Lazy::new(|| RailFence::new(true, 10));
+
+pub static KEY_3_0: Lazy<RailFenceKey> = Lazy::new(|| RailFenceKey::new(3, 0));
+pub static KEY_4_2: Lazy<RailFenceKey> = Lazy::new(|| RailFenceKey::new(4, 2));
+
+#[test]
+fn key_try_from() {
+  let key = |s| RailFenceKey::try_from((s, &*CONTEXT_SKIP_WHITESPACE));
+
+  assert_eq!(
+    key("3").map(|k| k.to_string()).ok(),
+    Some("3,0".to_string())
+  );
+  assert!(key("3,3").is_ok());
+  assert!(matches!(key("3,4"), Err(ParseError::InvalidOffset(4))));
+  assert!(matches!(key("0"), Err(ParseError::InvalidRails(0))));
+  assert!(matches!(key("1"), Err(ParseError::InvalidRails(1))));
+  assert!(matches!(key("x"), Err(ParseError::InvalidFormat(_))));
+}
+
+#[test]
+fn wikipedia_example() {
+  assert_eq!(
+    CONTEXT_SKIP_WHITESPACE.encipher("wearediscoveredrunatonce", &KEY_3_0),
+    "wecruoerdsoeerntneaivdac"
+  );
+}
+
+#[test]
+fn keys_iter() {
+  // Every rail count from 2 to 10 with each offset in its cycle.
+  let expected: usize = (2..=10).map(|rails| 2 * (rails - 1)).sum();
+
+  assert_eq!(CONTEXT_SKIP_WHITESPACE.keys_iter().count(), expected);
+}
+
+cipher_test!(
+  CONTEXT_INCLUDE_WHITESPACE,
+  *QUICK_BROWN_FOX,
+  KEY_3_0,
+  include_whitespace
+);
+
+cipher_test!(
+  CONTEXT_SKIP_WHITESPACE,
+  *QUICK_BROWN_FOX,
+  KEY_3_0,
+  skip_whitespace
+);
+
+cipher_test!(
+  CONTEXT_SKIP_WHITESPACE,
+  *QUICK_BROWN_FOX,
+  KEY_4_2,
+  skip_whitespace_offset
+);
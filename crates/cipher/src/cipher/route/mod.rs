@@ -0,0 +1,205 @@
+use crate::cipher::{Decipher, Encipher, KeysIterator, transposition};
+use cipher_derive::{IntoDecipherKey, IntoEncipherKey};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// How the grid is read out after the text is written into it row by row.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Path {
+  /// Clockwise from the top left corner, inwards.
+  Spiral,
+  /// Down the first column, up the second, and so on.
+  Boustrophedon,
+  /// Along the anti-diagonals from the top left, each top to bottom.
+  Diagonal,
+}
+
+const PATHS: [Path; 3] = [Path::Spiral, Path::Boustrophedon, Path::Diagonal];
+
+#[derive(
+  Debug, Clone, Serialize, Deserialize, IntoDecipherKey, IntoEncipherKey,
+)]
+pub struct RouteKey {
+  path: Path,
+  width: usize,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Route {
+  skip_whitespace: bool,
+  max_width: usize,
+}
+
+#[derive(Debug)]
+pub enum ParseError {
+  InvalidFormat(String),
+  InvalidPath(String),
+  InvalidWidth(usize),
+}
+
+impl fmt::Display for ParseError {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    match self {
+      ParseError::InvalidFormat(key) => {
+        write!(f, "Invalid key: '{key}', expected 'path,width'")
+      }
+      ParseError::InvalidPath(path) => write!(
+        f,
+        "Invalid path: '{path}', expected spiral, boustrophedon or diagonal"
+      ),
+      ParseError::InvalidWidth(width) => write!(f, "Invalid width: {width}"),
+    }
+  }
+}
+
+impl fmt::Display for Path {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    match self {
+      Path::Spiral => write!(f, "spiral"),
+      Path::Boustrophedon => write!(f, "boustrophedon"),
+      Path::Diagonal => write!(f, "diagonal"),
+    }
+  }
+}
+
+impl RouteKey {
+  pub fn try_new(path: Path, width: usize) -> Result<Self, ParseError> {
+    if width == 0 {
+      return Err(ParseError::InvalidWidth(width));
+    }
+
+    Ok(Self::new(path, width))
+  }
+
+  pub(crate) fn new(path: Path, width: usize) -> Self {
+    RouteKey { path, width }
+  }
+}
+
+impl TryFrom<(&str, &Route)> for RouteKey {
+  type Error = ParseError;
+
+  fn try_from((key, _): (&str, &Route)) -> Result<Self, Self::Error> {
+    let (path, width) = key
+      .split_once(',')
+      .ok_or_else(|| ParseError::InvalidFormat(key.to_string()))?;
+    let path = PATHS
+      .into_iter()
+      .find(|p| p.to_string() == path.trim())
+      .ok_or_else(|| ParseError::InvalidPath(path.to_string()))?;
+    let width = width
+      .trim()
+      .parse()
+      .map_err(|_| ParseError::InvalidFormat(key.to_string()))?;
+
+    Self::try_new(path, width)
+  }
+}
+
+impl fmt::Display for RouteKey {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(f, "{},{}", self.path, self.width)
+  }
+}
+
+impl Route {
+  /// `max_width` bounds the grid widths tried by `keys_iter`.
+  pub fn new(skip_whitespace: bool, max_width: usize) -> Self {
+    Route {
+      skip_whitespace,
+      max_width,
+    }
+  }
+}
+
+/// Positions of the written text in the order they are read out. The last
+/// row of the grid may be incomplete; its empty cells are skipped.
+fn read_positions(len: usize, key: &RouteKey) -> Vec<usize> {
+  let width = key.width;
+  let height = len.div_ceil(width);
+  let cell = |row: usize, column: usize| row * width + column;
+
+  let cells: Vec<usize> = match key.path {
+    Path::Spiral => {
+      let mut cells = Vec::with_capacity(width * height);
+      let (mut top, mut bottom, mut left, mut right) = (0, height, 0, width);
+      while top < bottom && left < right {
+        cells.extend((left..right).map(|c| cell(top, c)));
+        cells.extend((top + 1..bottom).map(|r| cell(r, right - 1)));
+        if top + 1 < bottom {
+          cells.extend((left..right - 1).rev().map(|c| cell(bottom - 1, c)));
+        }
+        if left + 1 < right {
+          cells.extend((top + 1..bottom - 1).rev().map(|r| cell(r, left)));
+        }
+        top += 1;
+        bottom -= 1;
+        left += 1;
+        right -= 1;
+      }
+      cells
+    }
+    Path::Boustrophedon => (0..width)
+      .flat_map(|c| {
+        let rows: Box<dyn Iterator<Item = usize>> = if c % 2 == 0 {
+          Box::new(0..height)
+        } else {
+          Box::new((0..height).rev())
+        };
+        rows.map(move |r| cell(r, c))
+      })
+      .collect(),
+    Path::Diagonal => (0..width + height)
+      .flat_map(|d| {
+        (0..height)
+          .filter(move |&r| r <= d && d - r < width)
+          .map(move |r| cell(r, d - r))
+      })
+      .collect(),
+  };
+
+  cells.into_iter().filter(|&i| i < len).collect()
+}
+
+impl Encipher for Route {
+  type Key = RouteKey;
+
+  fn encipher(&self, plaintext: &str, key: &Self::Key) -> String {
+    let letters = transposition::letters(plaintext, self.skip_whitespace);
+    let positions = read_positions(letters.len(), key);
+    let ciphertext = transposition::read(&letters, &positions);
+
+    transposition::reinsert(plaintext, self.skip_whitespace, ciphertext)
+  }
+}
+
+impl Decipher for Route {
+  type Key = RouteKey;
+
+  fn decipher(&self, ciphertext: &str, key: &Self::Key) -> String {
+    let letters = transposition::letters(ciphertext, self.skip_whitespace);
+    let positions = read_positions(letters.len(), key);
+    let plaintext = transposition::write(&letters, &positions);
+
+    transposition::reinsert(ciphertext, self.skip_whitespace, plaintext)
+  }
+}
+
+impl KeysIterator for Route {
+  type KeysIter =
+    <std::vec::Vec<RouteKey> as std::iter::IntoIterator>::IntoIter;
+
+  fn keys_iter(&self) -> Self::KeysIter {
+    let keys: Vec<RouteKey> = PATHS
+      .into_iter()
+      .flat_map(|path| {
+        (2..=self.max_width).map(move |width| RouteKey::new(path, width))
+      })
+      .collect();
+
+    keys.into_iter()
+  }
+}
+
+#[cfg(test)]
+mod tests;
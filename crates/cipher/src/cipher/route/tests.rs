@@ -0,0 +1,88 @@
+use super::*;
+use crate::cipher_test;
+use crate::tests::*;
+use once_cell::sync::Lazy;
+
+pub static CONTEXT_INCLUDE_WHITESPACE: Lazy<Route> =
+  Lazy::new(|| Route::new(false, 10));
+pub static CONTEXT_SKIP_WHITESPACE: Lazy<Route> =
+  Lazy::new(|| Route::new(true, 10));
+
+pub static KEY_SPIRAL: Lazy<RouteKey> =
+  Lazy::new(|| RouteKey::new(Path::Spiral, 6));
+pub static KEY_BOUSTROPHEDON: Lazy<RouteKey> =
+  Lazy::new(|| RouteKey::new(Path::Boustrophedon, 5));
+pub static KEY_DIAGONAL: Lazy<RouteKey> =
+  Lazy::new(|| RouteKey::new(Path::Diagonal, 4));
+
+#[test]
+fn key_try_from() {
+  let key = |s| RouteKey::try_from((s, &*CONTEXT_SKIP_WHITESPACE));
+
+  assert_eq!(
+    key("spiral,6").map(|k| k.to_string()).ok(),
+    Some("spiral,6".to_string())
+  );
+  assert!(matches!(key("zigzag,6"), Err(ParseError::InvalidPath(_))));
+  assert!(matches!(key("spiral,0"), Err(ParseError::InvalidWidth(0))));
+  assert!(matches!(key("spiral"), Err(ParseError::InvalidFormat(_))));
+}
+
+#[test]
+fn paths() {
+  // abcd
+  // efgh
+  // ij
+  let text = "abcdefghij";
+
+  assert_eq!(
+    CONTEXT_SKIP_WHITESPACE.encipher(text, &KEY_SPIRAL),
+    "abcdefjihg"
+  );
+  assert_eq!(
+    CONTEXT_SKIP_WHITESPACE.encipher(text, &RouteKey::new(Path::Spiral, 4)),
+    "abcdhjiefg"
+  );
+  assert_eq!(
+    CONTEXT_SKIP_WHITESPACE
+      .encipher(text, &RouteKey::new(Path::Boustrophedon, 4)),
+    "aeijfbcghd"
+  );
+  assert_eq!(
+    CONTEXT_SKIP_WHITESPACE.encipher(text, &KEY_DIAGONAL),
+    "abecfidgjh"
+  );
+}
+
+#[test]
+fn keys_iter() {
+  assert_eq!(CONTEXT_SKIP_WHITESPACE.keys_iter().count(), 3 * 9);
+}
+
+cipher_test!(
+  CONTEXT_INCLUDE_WHITESPACE,
+  *QUICK_BROWN_FOX,
+  KEY_SPIRAL,
+  include_whitespace
+);
+
+cipher_test!(
+  CONTEXT_SKIP_WHITESPACE,
+  *QUICK_BROWN_FOX,
+  KEY_SPIRAL,
+  spiral
+);
+
+cipher_test!(
+  CONTEXT_SKIP_WHITESPACE,
+  *QUICK_BROWN_FOX,
+  KEY_BOUSTROPHEDON,
+  boustrophedon
+);
+
+cipher_test!(
+  CONTEXT_SKIP_WHITESPACE,
+  *QUICK_BROWN_FOX,
+  KEY_DIAGONAL,
+  diagonal
+);
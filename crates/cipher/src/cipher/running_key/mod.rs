@@ -0,0 +1,110 @@
+mod solve;
+
+use crate::{
+  alphabet::Alphabet,
+  cipher::{Decipher, Encipher},
+};
+use cipher_derive::{IntoDecipherKey, IntoEncipherKey};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use stutter_zip::StutterZipIterator;
+
+pub use solve::CribCandidate;
+
+/// The letters of a key text, such as a book, without its spaces and
+/// punctuation.
+#[derive(
+  Debug, Clone, Serialize, Deserialize, IntoDecipherKey, IntoEncipherKey,
+)]
+pub struct RunningKeyKey(Vec<char>);
+
+/// Vigenère with a key as long as the text, so the key never repeats. Text
+/// past the end of the key is left unchanged.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RunningKey {
+  alphabet: Alphabet,
+  skip_whitespace: bool,
+}
+
+impl RunningKeyKey {
+  /// Keeps the letters of `text` in the alphabet, after lowercasing them.
+  pub fn from_text(text: &str, context: &RunningKey) -> Self {
+    RunningKeyKey::new(
+      text
+        .chars()
+        .flat_map(char::to_lowercase)
+        .filter(|&c| context.alphabet.contains(c))
+        .collect(),
+    )
+  }
+
+  pub fn new(letters: Vec<char>) -> Self {
+    RunningKeyKey(letters)
+  }
+
+  pub fn len(&self) -> usize {
+    self.0.len()
+  }
+
+  pub fn is_empty(&self) -> bool {
+    self.0.is_empty()
+  }
+
+  /// The `len` letters of the key from `offset` on, or fewer at its end.
+  pub fn fragment(&self, offset: usize, len: usize) -> Self {
+    let start = offset.min(self.0.len());
+    let end = offset.saturating_add(len).min(self.0.len());
+
+    RunningKeyKey(self.0[start..end].to_vec())
+  }
+}
+
+impl fmt::Display for RunningKeyKey {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(f, "{}", self.0.iter().collect::<String>())
+  }
+}
+
+impl RunningKey {
+  pub fn new(alphabet: Alphabet, skip_whitespace: bool) -> Self {
+    RunningKey {
+      alphabet,
+      skip_whitespace,
+    }
+  }
+
+  /// Number of key letters used by `text`.
+  pub fn key_len(&self, text: &str) -> usize {
+    text
+      .chars()
+      .filter(|c| !(c.is_whitespace() && self.skip_whitespace))
+      .count()
+  }
+}
+
+impl Encipher for RunningKey {
+  type Key = RunningKeyKey;
+
+  fn encipher(&self, plaintext: &str, key: &Self::Key) -> String {
+    plaintext
+      .chars()
+      .stutter_zip(key.0.iter(), |p| p.is_whitespace() && self.skip_whitespace)
+      .map(|(p, k)| k.map(|&k| self.alphabet.add(p, k)).unwrap_or(p))
+      .collect()
+  }
+}
+
+impl Decipher for RunningKey {
+  type Key = RunningKeyKey;
+
+  fn decipher(&self, ciphertext: &str, key: &Self::Key) -> String {
+    ciphertext
+      .chars()
+      .stutter_zip(key.0.iter(), |c| c.is_whitespace() && self.skip_whitespace)
+      .map(|(c, k)| k.map(|&k| self.alphabet.sub(c, k)).unwrap_or(c))
+      .collect()
+  }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,94 @@
+use super::{RunningKey, RunningKeyKey};
+use crate::cipher::Decipher;
+use crate::language::GetConfidence;
+use crate::periodicity;
+use rayon::prelude::*;
+
+/// A crib tried at one position of the ciphertext, with the fragment it
+/// implies: the key there if the crib is plaintext, or the plaintext if
+/// the crib is part of the key.
+#[derive(Debug, Clone)]
+pub struct CribCandidate {
+  pub position: usize,
+  pub fragment: String,
+  pub confidence: f64,
+}
+
+impl RunningKey {
+  /// Deciphers `ciphertext` with `book` from every offset that leaves it
+  /// enough letters, in parallel on the current rayon pool, and ranks the
+  /// offsets by the confidence score of the plaintext, best first. A book
+  /// shorter than the key the ciphertext needs has no offsets.
+  pub fn search_offsets(
+    &self,
+    ciphertext: &str,
+    book: &RunningKeyKey,
+    get_confidence: &GetConfidence,
+  ) -> Vec<(usize, f64)> {
+    let key_len = self.key_len(ciphertext);
+    let Some(last) = book.len().checked_sub(key_len) else {
+      return Vec::new();
+    };
+
+    let mut offsets: Vec<_> = (0..=last)
+      .into_par_iter()
+      .map(|offset| {
+        let key = book.fragment(offset, key_len);
+        (offset, get_confidence.run(&self.decipher(ciphertext, &key)))
+      })
+      .collect();
+    offsets.sort_by(|a, b| a.1.total_cmp(&b.1));
+
+    offsets
+  }
+
+  /// Drags `crib` along `ciphertext`, ranking every position where it fits
+  /// by the confidence score of the implied fragment, best first. With a
+  /// key taken from a book, both plaintext and key are language, so the
+  /// fragment should read like it where the crib is right. Positions count
+  /// the characters that use a key letter.
+  pub fn crib_drag(
+    &self,
+    ciphertext: &str,
+    crib: &str,
+    get_confidence: &GetConfidence,
+  ) -> Vec<CribCandidate> {
+    let stream =
+      periodicity::key_stream(ciphertext, &self.alphabet, self.skip_whitespace);
+    let crib: Vec<usize> = crib
+      .chars()
+      .filter_map(|c| self.alphabet.get_index(c))
+      .collect();
+    if crib.is_empty() {
+      return Vec::new();
+    }
+
+    let mut candidates: Vec<_> = stream
+      .windows(crib.len())
+      .enumerate()
+      .filter_map(|(position, window)| {
+        let fragment = window
+          .iter()
+          .zip(&crib)
+          .map(|(c, p)| {
+            let c = (*c)?;
+            self
+              .alphabet
+              .get((self.alphabet.len() + c - p) % self.alphabet.len())
+          })
+          .collect::<Option<String>>()?;
+        let confidence = get_confidence.run(&fragment);
+
+        Some(CribCandidate {
+          position,
+          fragment,
+          confidence,
+        })
+      })
+      .collect();
+
+    candidates.sort_by(|a, b| a.confidence.total_cmp(&b.confidence));
+
+    candidates
+  }
+}
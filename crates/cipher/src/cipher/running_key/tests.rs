@@ -0,0 +1,74 @@
+use super::*;
+use crate::alphabet::Alphabet;
+use crate::cipher_test;
+use crate::tests::*;
+use once_cell::sync::Lazy;
+
+pub static CONTEXT: Lazy<RunningKey> =
+  Lazy::new(|| RunningKey::new(Alphabet::latin(), true));
+
+pub static KEY_A: Lazy<RunningKeyKey> =
+  Lazy::new(|| RunningKeyKey::from_text(&A_TALE_OF_TWO_CITIES, &CONTEXT));
+
+// The fox without its trailing newline.
+static FOX: Lazy<String> = Lazy::new(|| QUICKBROWNFOX.trim().to_string());
+
+#[test]
+fn from_text() {
+  let key =
+    RunningKeyKey::from_text("It was the best of times, it was…", &CONTEXT);
+
+  assert_eq!(key.to_string(), "itwasthebestoftimesitwas");
+  assert_eq!(key.fragment(2, 3).to_string(), "was");
+  assert_eq!(key.fragment(20, 10).to_string(), "twas");
+}
+
+#[test]
+fn key_runs_out() {
+  let key = RunningKeyKey::new(vec!['b', 'c']);
+
+  assert_eq!(CONTEXT.encipher("a aaa", &key), "b caa");
+  assert_eq!(CONTEXT.key_len("a aaa"), 4);
+}
+
+cipher_test!(CONTEXT, *QUICK_BROWN_FOX, KEY_A, skip_whitespace);
+
+#[test]
+fn crib_drag() {
+  use crate::language::GetConfidence;
+  use std::sync::Arc;
+
+  let ciphertext = CONTEXT.encipher(&FOX, &KEY_A);
+  let table = ENGLISH.log_ngram_table(3).unwrap();
+  let get_confidence =
+    GetConfidence::new(Arc::new(move |text: &str| table.text_confidence(text)));
+
+  let candidates =
+    CONTEXT.crib_drag(&ciphertext, "quickbrown", &get_confidence);
+  assert_eq!(candidates.len(), FOX.len() - 9);
+  assert_eq!(candidates[0].position, 3);
+  assert_eq!(candidates[0].fragment, "asthebesto");
+}
+
+#[test]
+fn search_offsets() {
+  use crate::language::GetConfidence;
+  use std::sync::Arc;
+
+  let key_len = CONTEXT.key_len(&FOX);
+  let ciphertext = CONTEXT.encipher(&FOX, &KEY_A.fragment(100, key_len));
+  let table = ENGLISH.log_ngram_table(3).unwrap();
+  let get_confidence =
+    GetConfidence::new(Arc::new(move |text: &str| table.text_confidence(text)));
+
+  let offsets = CONTEXT.search_offsets(&ciphertext, &KEY_A, &get_confidence);
+  assert_eq!(offsets.len(), KEY_A.len() - key_len + 1);
+  assert_eq!(offsets[0].0, 100);
+
+  let short = KEY_A.fragment(0, key_len - 1);
+  assert!(
+    CONTEXT
+      .search_offsets(&ciphertext, &short, &get_confidence)
+      .is_empty()
+  );
+}
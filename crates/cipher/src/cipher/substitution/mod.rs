@@ -1,6 +1,7 @@
 pub mod quipqiup;
+mod solve;
 
-use crate::alphabet::Alphabet;
+use crate::alphabet::{self, Alphabet};
 use crate::cipher::{
   Decipher, Encipher, IntoDecipherKey, IntoEncipherKey, PartialDecipher,
 };
@@ -62,7 +63,7 @@ impl TryFrom<SubstitutionKeyDeserializer> for SubstitutionKey {
 impl From<SubstitutionKey> for SubstitutionKeySerializer {
   fn from(value: SubstitutionKey) -> Self {
     let mut pairs: Vec<_> = value.0.iter().collect();
-    pairs.sort_by(|(k1, _), (k2, _)| k1.cmp(k2));
+    pairs.sort_by_key(|(k1, _)| *k1);
     let (alphabet, key) = pairs.into_iter().unzip();
 
     SubstitutionKeySerializer { alphabet, key }
@@ -98,6 +99,20 @@ impl SubstitutionEncipherKey {
   pub fn new(key: String, alphabet: &Alphabet) -> Self {
     SubstitutionKey::new(key, alphabet).into()
   }
+
+  /// The key written as the alphabet mixed with `keyword`, see
+  /// `Alphabet::keyword_mixed`.
+  pub fn from_keyword(
+    keyword: &str,
+    columnar: bool,
+    context: &Substitution,
+  ) -> Result<Self, ParseError> {
+    let mixed = context.alphabet.keyword_mixed(keyword, columnar).map_err(
+      |alphabet::ParseError::InvalidChar(c)| ParseError::InvalidChar(c),
+    )?;
+
+    Self::try_new(mixed.iter().collect(), context)
+  }
 }
 
 impl IntoDecipherKey for SubstitutionEncipherKey {
@@ -232,6 +247,25 @@ impl SubstitutionPartialDecipherKey {
   pub fn new(key: AHashMap<char, Option<char>>) -> Self {
     SubstitutionPartialDecipherKey(key)
   }
+
+  pub fn to_decipher_key(&self) -> Option<SubstitutionDecipherKey> {
+    self
+      .0
+      .iter()
+      .map(|(c, p)| p.map(|p| (*c, p)))
+      .collect::<Option<AHashMap<_, _>>>()
+      .map(SubstitutionDecipherKey)
+  }
+}
+
+impl fmt::Display for SubstitutionPartialDecipherKey {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    let mut pairs: Vec<_> = self.0.iter().collect();
+    pairs.sort_by_key(|(c, _)| **c);
+    pairs
+      .into_iter()
+      .try_for_each(|(_, p)| write!(f, "{}", p.unwrap_or('?')))
+  }
 }
 
 impl PartialDecipher for Substitution {
@@ -1,13 +1,42 @@
-use super::Substitution;
+use super::{
+  Substitution, SubstitutionDecipherKey, SubstitutionPartialDecipherKey,
+};
 use crate::alphabet::Alphabet;
 use ahash::AHashMap;
 use serde::{Deserialize, Serialize};
 
+// Upper bound on the number of search nodes visited per solve, so that
+// ciphertexts with many short, ambiguous words still terminate promptly.
+const SEARCH_LIMIT: usize = 2_000_000;
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct QuipqiupContext {
   table: AHashMap<String, Vec<String>>,
 }
 
+#[derive(Debug, Clone)]
+pub struct QuipqiupSolution {
+  pub key: SubstitutionPartialDecipherKey,
+  pub matched_letters: usize,
+}
+
+struct CipherWord {
+  chars: Vec<usize>,
+  candidates: Vec<Vec<usize>>,
+  weight: usize,
+}
+
+struct Search<'a> {
+  words: &'a [CipherWord],
+  // Total weight of words[i..], used as an optimistic bound.
+  remaining: Vec<usize>,
+  decipher: Vec<Option<usize>>,
+  encipher: Vec<Option<usize>>,
+  solutions: Vec<(Vec<Option<usize>>, usize)>,
+  max_solutions: usize,
+  nodes: usize,
+}
+
 impl QuipqiupContext {
   pub fn new(words: Vec<String>) -> Self {
     let mut table: AHashMap<String, Vec<String>> = AHashMap::new();
@@ -19,6 +48,14 @@ impl QuipqiupContext {
     QuipqiupContext { table }
   }
 
+  pub fn candidates(&self, word: &str) -> &[String] {
+    self
+      .table
+      .get(&Self::normalize_word(word))
+      .map(|x| x.as_slice())
+      .unwrap_or(&[])
+  }
+
   fn normalize_word(word: &str) -> String {
     let mut normalization: AHashMap<char, char> = AHashMap::new();
     let latin = Alphabet::latin();
@@ -40,9 +77,192 @@ impl QuipqiupContext {
   }
 }
 
+impl QuipqiupSolution {
+  pub fn decipher_key(&self) -> Option<SubstitutionDecipherKey> {
+    self.key.to_decipher_key()
+  }
+}
+
+impl Search<'_> {
+  fn run(&mut self, i: usize, score: usize) {
+    self.nodes += 1;
+    if self.nodes > SEARCH_LIMIT {
+      return;
+    }
+
+    if i == self.words.len() {
+      self.record(score);
+      return;
+    }
+
+    if self.solutions.len() == self.max_solutions
+      && self
+        .solutions
+        .last()
+        .is_some_and(|(_, worst)| score + self.remaining[i] <= *worst)
+    {
+      return;
+    }
+
+    let words = self.words;
+    let word = &words[i];
+    for candidate in &word.candidates {
+      let mut assigned = Vec::new();
+      if self.assign(&word.chars, candidate, &mut assigned) {
+        self.run(i + 1, score + word.weight);
+      }
+      for c in assigned {
+        if let Some(p) = self.decipher[c].take() {
+          self.encipher[p] = None;
+        }
+      }
+    }
+
+    // Leave the word unsolved, e.g. a proper noun missing from the word list.
+    self.run(i + 1, score);
+  }
+
+  fn assign(
+    &mut self,
+    cipher: &[usize],
+    plain: &[usize],
+    assigned: &mut Vec<usize>,
+  ) -> bool {
+    for (&c, &p) in cipher.iter().zip(plain) {
+      match (self.decipher[c], self.encipher[p]) {
+        (Some(q), _) if q == p => (),
+        (None, None) => {
+          self.decipher[c] = Some(p);
+          self.encipher[p] = Some(c);
+          assigned.push(c);
+        }
+        _ => return false,
+      }
+    }
+
+    true
+  }
+
+  fn record(&mut self, score: usize) {
+    if self.solutions.iter().any(|(key, _)| *key == self.decipher) {
+      return;
+    }
+
+    let position = self
+      .solutions
+      .partition_point(|(_, other_score)| *other_score >= score);
+    if position >= self.max_solutions {
+      return;
+    }
+
+    self
+      .solutions
+      .insert(position, (self.decipher.clone(), score));
+    self.solutions.truncate(self.max_solutions);
+  }
+}
+
 impl Substitution {
-  pub fn quipqiup(&self, _quipqiup_context: QuipqiupContext, ciphertext: &str) {
-    let _words: Vec<_> = ciphertext.split_whitespace().collect();
-    //TODO
+  pub fn quipqiup(
+    &self,
+    quipqiup_context: &QuipqiupContext,
+    ciphertext: &str,
+    max_solutions: usize,
+  ) -> Vec<QuipqiupSolution> {
+    let mut counts: AHashMap<String, usize> = AHashMap::new();
+    for word in ciphertext.split_whitespace() {
+      let word: String = word
+        .chars()
+        .filter(|&c| self.alphabet.contains(c))
+        .collect();
+      if !word.is_empty() {
+        *counts.entry(word).or_insert(0) += 1;
+      }
+    }
+
+    let mut words: Vec<_> = counts
+      .into_iter()
+      .filter_map(|(word, count)| {
+        self.cipher_word(quipqiup_context, &word, count)
+      })
+      .collect();
+
+    // Most constrained words first, so contradictions surface early.
+    words.sort_by(|a, b| {
+      a.candidates
+        .len()
+        .cmp(&b.candidates.len())
+        .then(b.chars.len().cmp(&a.chars.len()))
+        .then(a.chars.cmp(&b.chars))
+    });
+
+    let mut remaining = vec![0; words.len() + 1];
+    for i in (0..words.len()).rev() {
+      remaining[i] = remaining[i + 1] + words[i].weight;
+    }
+
+    let mut search = Search {
+      words: &words,
+      remaining,
+      decipher: vec![None; self.alphabet.len()],
+      encipher: vec![None; self.alphabet.len()],
+      solutions: Vec::new(),
+      max_solutions,
+      nodes: 0,
+    };
+    search.run(0, 0);
+
+    search
+      .solutions
+      .into_iter()
+      .map(|(decipher, matched_letters)| QuipqiupSolution {
+        key: SubstitutionPartialDecipherKey::new(
+          decipher
+            .iter()
+            .enumerate()
+            .filter_map(|(c, p)| {
+              Some((
+                self.alphabet.get(c)?,
+                p.and_then(|p| self.alphabet.get(p)),
+              ))
+            })
+            .collect(),
+        ),
+        matched_letters,
+      })
+      .collect()
+  }
+
+  fn cipher_word(
+    &self,
+    quipqiup_context: &QuipqiupContext,
+    word: &str,
+    count: usize,
+  ) -> Option<CipherWord> {
+    let chars: Option<Vec<_>> =
+      word.chars().map(|c| self.alphabet.get_index(c)).collect();
+    let chars = chars?;
+
+    let candidates: Vec<Vec<usize>> = quipqiup_context
+      .candidates(word)
+      .iter()
+      .filter_map(|candidate| {
+        candidate
+          .chars()
+          .map(|p| self.alphabet.get_index(p))
+          .collect::<Option<Vec<_>>>()
+      })
+      .filter(|candidate| candidate.len() == chars.len())
+      .collect();
+
+    if candidates.is_empty() {
+      return None;
+    }
+
+    Some(CipherWord {
+      weight: chars.len() * count,
+      chars,
+      candidates,
+    })
   }
 }
@@ -0,0 +1,104 @@
+use super::{Substitution, SubstitutionDecipherKey};
+use crate::language::{GetConfidence, Language};
+use crate::search::{self, AnnealOptions};
+use ahash::AHashMap;
+use rand::{Rng, RngExt, seq::SliceRandom};
+
+impl Substitution {
+  pub fn random_key<R: Rng>(&self, rng: &mut R) -> SubstitutionDecipherKey {
+    let mut plain: Vec<_> = self.alphabet.iter().collect();
+    plain.shuffle(rng);
+
+    SubstitutionDecipherKey(self.alphabet.iter().zip(plain).collect())
+  }
+
+  /// Pairs ciphertext letters with plaintext letters of the same frequency
+  /// rank in `language`. Letters the language has no statistics for are
+  /// ranked last, in alphabet order.
+  pub fn frequency_key(
+    &self,
+    ciphertext: &str,
+    language: &Language,
+  ) -> SubstitutionDecipherKey {
+    let mut counts: AHashMap<char, usize> = AHashMap::new();
+    for c in ciphertext.chars().filter(|&c| self.alphabet.contains(c)) {
+      *counts.entry(c).or_insert(0) += 1;
+    }
+
+    let mut cipher: Vec<_> = self.alphabet.iter().collect();
+    cipher.sort_by_key(|c| std::cmp::Reverse(counts.get(c).copied()));
+
+    // Ranked n-grams are ordered from least to most common.
+    let mut plain: Vec<char> = language
+      .char_ngrams(1)
+      .into_iter()
+      .flat_map(|unigrams| unigrams.ngrams())
+      .filter_map(|ngram| ngram.chars().next())
+      .filter(|&c| self.alphabet.contains(c))
+      .collect();
+    plain.reverse();
+    let unranked: Vec<_> = self
+      .alphabet
+      .iter()
+      .filter(|c| !plain.contains(c))
+      .collect();
+    plain.extend(unranked);
+
+    SubstitutionDecipherKey(cipher.into_iter().zip(plain).collect())
+  }
+
+  /// Improves `key` by swapping pairs of plaintext letters, keeping swaps
+  /// that lower the confidence score of the deciphered text.
+  pub fn hill_climb<R: Rng>(
+    &self,
+    ciphertext: &str,
+    key: SubstitutionDecipherKey,
+    get_confidence: &GetConfidence,
+    options: &AnnealOptions,
+    rng: &mut R,
+  ) -> (SubstitutionDecipherKey, f64) {
+    let cipher: Vec<_> = self.alphabet.iter().collect();
+    let plain: Vec<_> = cipher
+      .iter()
+      .map(|c| key.0.get(c).copied().unwrap_or(*c))
+      .collect();
+
+    let (plain, score) = search::anneal(
+      plain,
+      options,
+      rng,
+      |plain, rng| {
+        let mut plain = plain.clone();
+        let i = rng.random_range(0..plain.len());
+        let j = rng.random_range(0..plain.len());
+        plain.swap(i, j);
+        plain
+      },
+      |plain| {
+        let text: String = ciphertext
+          .chars()
+          .map(|c| match self.alphabet.get_index(c) {
+            Some(i) => plain[i],
+            None => c,
+          })
+          .collect();
+        get_confidence.run(&text)
+      },
+    );
+
+    (
+      SubstitutionDecipherKey(cipher.into_iter().zip(plain).collect()),
+      score,
+    )
+  }
+
+  /// Formats `key` in the same form `SubstitutionEncipherKey` is parsed
+  /// from, so solved keys can be passed back to `decipher`.
+  pub fn key_string(&self, key: &SubstitutionDecipherKey) -> String {
+    self
+      .alphabet
+      .iter()
+      .map(|c| key.0.get(&c).copied().unwrap_or(c))
+      .collect()
+  }
+}
@@ -57,4 +57,127 @@ fn key_try_new() {
   );
 }
 
+#[test]
+fn from_keyword() {
+  let key = |keyword: &str, columnar: bool| {
+    SubstitutionEncipherKey::from_keyword(keyword, columnar, &CONTEXT)
+      .map(|key| CONTEXT.key_string(&key.into_decipher_key()))
+  };
+
+  assert_eq!(key("kryptos", false).unwrap(), "kryptosabcdefghijlmnquvwxz");
+  assert_eq!(key("zebras", true).unwrap(), "ahnvbfltedkqyrgmusiowzcjpx");
+  assert_eq!(key("", true).unwrap(), "abcdefghijklmnopqrstuvwxyz");
+  assert!(matches!(
+    key("k2", false),
+    Err(ParseError::InvalidChar('2'))
+  ));
+}
+
 cipher_test!(CONTEXT, *QUICK_BROWN_FOX, KEY_A, include_whitespace);
+
+#[test]
+fn quipqiup() {
+  let words = QUICK_BROWN_FOX
+    .split_whitespace()
+    .chain([
+      "fax", "quack", "brawn", "jumbo", "ever", "then", "lady", "dig",
+    ])
+    .map(|word| word.to_string())
+    .collect();
+  let quipqiup_context = quipqiup::QuipqiupContext::new(words);
+  let ciphertext = CONTEXT.encipher(&QUICK_BROWN_FOX, &KEY_A);
+
+  let solutions = CONTEXT.quipqiup(&quipqiup_context, &ciphertext, 5);
+  assert!(solutions.iter().all(|s| s.matched_letters <= 35));
+
+  // "quick"/"jumps" and "fox"/"dog" share letter patterns, so several keys
+  // match every word; the right one must be among them.
+  let solution = solutions
+    .iter()
+    .find(|s| CONTEXT.partial_decipher(&ciphertext, &s.key) == *QUICK_BROWN_FOX)
+    .expect("quipqiup did not find the plaintext");
+  assert_eq!(solution.matched_letters, 35);
+
+  // The ciphertext is a pangram, so every letter of the key is recovered.
+  let key = solution
+    .decipher_key()
+    .expect("pangram should give a full key");
+  assert_eq!(CONTEXT.decipher(&ciphertext, &key), *QUICK_BROWN_FOX);
+}
+
+#[test]
+fn hill_climb() {
+  use crate::language::GetConfidence;
+  use crate::search::AnnealOptions;
+  use rand::{SeedableRng, rngs::StdRng};
+  use std::sync::Arc;
+
+  let ciphertext = CONTEXT.encipher(&QUICK_BROWN_FOX, &KEY_A);
+  let get_confidence = GetConfidence::new(Arc::new(|text: &str| {
+    text
+      .chars()
+      .zip(QUICK_BROWN_FOX.chars())
+      .filter(|(a, b)| a != b)
+      .count() as f64
+  }));
+  let options = AnnealOptions {
+    iterations: 20000,
+    temperature: 0.0,
+  };
+
+  let mut rng = StdRng::seed_from_u64(0);
+  let key = CONTEXT.random_key(&mut rng);
+  let (key, confidence) =
+    CONTEXT.hill_climb(&ciphertext, key, &get_confidence, &options, &mut rng);
+
+  assert_eq!(confidence, 0.0);
+  assert_eq!(CONTEXT.decipher(&ciphertext, &key), *QUICK_BROWN_FOX);
+  assert_eq!(CONTEXT.key_string(&key), "zebrascdfghijklmnopqtuvwxy");
+}
+
+#[test]
+fn frequency_key() {
+  use crate::language::Language;
+  use crate::ngrams::RankedNgrams;
+
+  let unigrams = RankedNgrams::from_iter(
+    [("e", 3), ("t", 2), ("a", 1)]
+      .into_iter()
+      .map(|(ngram, count)| (ngram.to_string(), count as u64)),
+  );
+  let language = Language::new(
+    RankedNgrams::default(),
+    AHashMap::from([(1, unigrams)]),
+    0.0,
+  );
+
+  let key = CONTEXT.frequency_key("xxxqqy", &language);
+  assert_eq!(CONTEXT.decipher("xqy", &key), "eta");
+}
+
+#[test]
+fn hill_climb_log_trigrams() {
+  use crate::language::GetConfidence;
+  use crate::search::AnnealOptions;
+  use rand::{SeedableRng, rngs::StdRng};
+  use std::sync::Arc;
+
+  let ciphertext = CONTEXT.encipher(&A_TALE_OF_TWO_CITIES, &KEY_A);
+  let table = ENGLISH.log_ngram_table(3).unwrap();
+  let get_confidence =
+    GetConfidence::new(Arc::new(move |text: &str| table.text_confidence(text)));
+  let options = AnnealOptions {
+    iterations: 10000,
+    temperature: 0.0,
+  };
+
+  let mut rng = StdRng::seed_from_u64(0);
+  let solved = (0..5).any(|_| {
+    let key = CONTEXT.frequency_key(&ciphertext, &ENGLISH);
+    let (key, _) =
+      CONTEXT.hill_climb(&ciphertext, key, &get_confidence, &options, &mut rng);
+    CONTEXT.decipher(&ciphertext, &key) == *A_TALE_OF_TWO_CITIES
+  });
+
+  assert!(solved);
+}
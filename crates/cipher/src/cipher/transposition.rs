@@ -0,0 +1,48 @@
+//! Helpers shared by the transposition ciphers, which rearrange letters
+//! without changing them.
+
+/// The letters of `text` that take part in the transposition.
+pub(crate) fn letters(text: &str, skip_whitespace: bool) -> Vec<char> {
+  text
+    .chars()
+    .filter(|c| !(c.is_whitespace() && skip_whitespace))
+    .collect()
+}
+
+/// Puts `letters` back in place of the letters of `text`, leaving skipped
+/// whitespace where it was. Letters left over are appended.
+pub(crate) fn reinsert(
+  text: &str,
+  skip_whitespace: bool,
+  letters: Vec<char>,
+) -> String {
+  let mut letters = letters.into_iter();
+  let mut result: String = text
+    .chars()
+    .map(|c| {
+      if c.is_whitespace() && skip_whitespace {
+        c
+      } else {
+        letters.next().unwrap_or(c)
+      }
+    })
+    .collect();
+  result.extend(letters);
+
+  result
+}
+
+/// Reads `letters` in the order given by `positions`.
+pub(crate) fn read(letters: &[char], positions: &[usize]) -> Vec<char> {
+  positions.iter().map(|&i| letters[i]).collect()
+}
+
+/// Undoes `read`, writing each letter back to its position.
+pub(crate) fn write(letters: &[char], positions: &[usize]) -> Vec<char> {
+  let mut result = letters.to_vec();
+  for (&c, &i) in letters.iter().zip(positions) {
+    result[i] = c;
+  }
+
+  result
+}
@@ -0,0 +1,156 @@
+use crate::{
+  alphabet::Alphabet,
+  cipher::{Decipher, Encipher, fractionation},
+  periodicity::{self, PeriodCandidate},
+};
+use cipher_derive::{IntoDecipherKey, IntoEncipherKey};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+// Letters in a 3x3x3 cube.
+const CUBE_LEN: usize = 27;
+
+/// The letters of the cube in reading order: layer, row, then column.
+#[derive(
+  Debug, Clone, Serialize, Deserialize, IntoDecipherKey, IntoEncipherKey,
+)]
+pub struct TrifidKey(Vec<char>);
+
+/// Bifid in three dimensions: every letter is split into its layer, row and
+/// column in a keyed cube of 27 letters. A period of zero uses the whole
+/// text as one block. Letters not in the alphabet are dropped.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Trifid {
+  alphabet: Alphabet,
+  period: usize,
+}
+
+#[derive(Debug)]
+pub enum ParseError {
+  InvalidChar(char),
+  NotCube(usize),
+}
+
+impl fmt::Display for ParseError {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    match self {
+      ParseError::InvalidChar(c) => write!(f, "Invalid character: '{c}'"),
+      ParseError::NotCube(len) => {
+        write!(f, "Alphabet has {len} letters, a cube needs {CUBE_LEN}")
+      }
+    }
+  }
+}
+
+impl TrifidKey {
+  /// Fills the cube with the letters of `keyword`, then the rest of the
+  /// alphabet, skipping repeated letters.
+  pub fn try_new(keyword: &str, context: &Trifid) -> Result<Self, ParseError> {
+    if context.alphabet.len() != CUBE_LEN {
+      return Err(ParseError::NotCube(context.alphabet.len()));
+    }
+    if let Some(c) = keyword.chars().find(|&c| !context.alphabet.contains(c)) {
+      return Err(ParseError::InvalidChar(c));
+    }
+
+    let mut letters: Vec<char> = Vec::with_capacity(CUBE_LEN);
+    keyword
+      .chars()
+      .chain(context.alphabet.iter())
+      .for_each(|c| {
+        if !letters.contains(&c) {
+          letters.push(c);
+        }
+      });
+
+    Ok(TrifidKey::new(letters))
+  }
+
+  pub fn new(letters: Vec<char>) -> Self {
+    TrifidKey(letters)
+  }
+
+  /// Layer, row and column of every letter of `text` in the cube.
+  fn coordinates(&self, text: &str) -> Vec<[usize; 3]> {
+    text
+      .chars()
+      .filter_map(|c| self.0.iter().position(|&x| x == c))
+      .map(|i| [i / 9, (i / 3) % 3, i % 3])
+      .collect()
+  }
+
+  fn letters(&self, coordinates: &[[usize; 3]]) -> String {
+    coordinates
+      .iter()
+      .filter_map(|[layer, row, column]| {
+        self.0.get(layer * 9 + row * 3 + column).copied()
+      })
+      .collect()
+  }
+}
+
+impl TryFrom<(&str, &Trifid)> for TrifidKey {
+  type Error = ParseError;
+
+  fn try_from((key, context): (&str, &Trifid)) -> Result<Self, Self::Error> {
+    Self::try_new(key, context)
+  }
+}
+
+impl fmt::Display for TrifidKey {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(f, "{}", self.0.iter().collect::<String>())
+  }
+}
+
+impl Trifid {
+  /// `alphabet` must have exactly 27 letters, e.g. Latin plus a filler
+  /// symbol built with `Alphabet::from_iter`.
+  pub fn new(alphabet: Alphabet, period: usize) -> Self {
+    Trifid { alphabet, period }
+  }
+
+  /// Ranks the likely periods of `ciphertext`, best first.
+  pub fn periods(
+    &self,
+    ciphertext: &str,
+    max_period: usize,
+  ) -> Vec<PeriodCandidate> {
+    let letters: Vec<usize> = ciphertext
+      .chars()
+      .filter_map(|c| self.alphabet.get_index(c))
+      .collect();
+
+    periodicity::rank_fractionation_periods(
+      &letters,
+      self.alphabet.len(),
+      3,
+      max_period,
+    )
+  }
+}
+
+impl Encipher for Trifid {
+  type Key = TrifidKey;
+
+  fn encipher(&self, plaintext: &str, key: &Self::Key) -> String {
+    let coordinates = key.coordinates(plaintext);
+    let coordinates = fractionation::fractionate(&coordinates, self.period);
+
+    key.letters(&coordinates)
+  }
+}
+
+impl Decipher for Trifid {
+  type Key = TrifidKey;
+
+  fn decipher(&self, ciphertext: &str, key: &Self::Key) -> String {
+    let coordinates = key.coordinates(ciphertext);
+    let coordinates = fractionation::unfractionate(&coordinates, self.period);
+
+    key.letters(&coordinates)
+  }
+}
+
+#[cfg(test)]
+mod tests;
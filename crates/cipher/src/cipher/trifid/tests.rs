@@ -0,0 +1,57 @@
+use super::*;
+use crate::alphabet::Alphabet;
+use crate::cipher_test;
+use crate::tests::*;
+use once_cell::sync::Lazy;
+
+static ALPHABET: Lazy<Alphabet> =
+  Lazy::new(|| Alphabet::latin().iter().chain(['+']).collect());
+
+pub static CONTEXT: Lazy<Trifid> =
+  Lazy::new(|| Trifid::new(ALPHABET.clone(), 5));
+
+pub static KEY_A: Lazy<TrifidKey> =
+  Lazy::new(|| TrifidKey::try_new("felixmariedelastelle", &CONTEXT).unwrap());
+
+static TALE: Lazy<String> = Lazy::new(|| {
+  A_TALE_OF_TWO_CITIES
+    .chars()
+    .filter(|&c| ALPHABET.contains(c))
+    .collect()
+});
+
+// The fox without its trailing newline, which the cube leaves out.
+static FOX: Lazy<String> = Lazy::new(|| QUICKBROWNFOX.trim().to_string());
+
+#[test]
+fn key_try_new() {
+  assert!(matches!(
+    TrifidKey::try_new("gr8", &CONTEXT),
+    Err(ParseError::InvalidChar('8')),
+  ));
+  assert!(matches!(
+    TrifidKey::try_new("key", &Trifid::new(Alphabet::latin(), 5)),
+    Err(ParseError::NotCube(26)),
+  ));
+}
+
+#[test]
+fn wikipedia_example() {
+  assert_eq!(
+    CONTEXT.encipher("aidetoilecieltaidera", &KEY_A),
+    "fmjfvoissuftfpufeqqc"
+  );
+}
+
+#[test]
+fn periods() {
+  for period in [5, 7, 9] {
+    let context = Trifid::new(ALPHABET.clone(), period);
+    let ciphertext = context.encipher(&TALE, &KEY_A);
+
+    assert_eq!(context.decipher(&ciphertext, &KEY_A), *TALE);
+    assert_eq!(context.periods(&ciphertext, 12)[0].period, period);
+  }
+}
+
+cipher_test!(CONTEXT, *FOX, KEY_A, period_5);
@@ -0,0 +1,159 @@
+use crate::{
+  alphabet::Alphabet,
+  cipher::{Decipher, Encipher, digraphic},
+  language::GetConfidence,
+  polybius::{ParseError, PolybiusSquare},
+  search::AnnealOptions,
+};
+use cipher_derive::{IntoDecipherKey, IntoEncipherKey};
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// Two keyed squares, written as their keywords separated by a comma.
+#[derive(
+  Debug, Clone, Serialize, Deserialize, IntoDecipherKey, IntoEncipherKey,
+)]
+pub struct TwoSquareKey(PolybiusSquare, PolybiusSquare);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Orientation {
+  /// The first square left of the second. Pairs in the same row come out
+  /// reversed.
+  Horizontal,
+  /// The first square above the second. Pairs in the same column come out
+  /// unchanged.
+  Vertical,
+}
+
+/// Enciphers pairs of letters, the first found in the first square and the
+/// second in the other, by the opposite corners of the rectangle they span.
+/// Letters not in the squares are dropped and an odd final letter is padded
+/// with `filler`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TwoSquare {
+  alphabet: Alphabet,
+  merge: Option<(char, char)>,
+  filler: char,
+  orientation: Orientation,
+}
+
+impl TwoSquareKey {
+  pub fn try_new(key: &str, context: &TwoSquare) -> Result<Self, ParseError> {
+    let (first, second) = key.split_once(',').unwrap_or((key, ""));
+    let square = |keyword: &str| {
+      PolybiusSquare::new(keyword.trim(), &context.alphabet, context.merge)
+    };
+
+    Ok(TwoSquareKey::new(square(first)?, square(second)?))
+  }
+
+  pub fn new(first: PolybiusSquare, second: PolybiusSquare) -> Self {
+    TwoSquareKey(first, second)
+  }
+}
+
+impl TryFrom<(&str, &TwoSquare)> for TwoSquareKey {
+  type Error = ParseError;
+
+  fn try_from((key, context): (&str, &TwoSquare)) -> Result<Self, Self::Error> {
+    Self::try_new(key, context)
+  }
+}
+
+impl fmt::Display for TwoSquareKey {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(f, "{},{}", self.0, self.1)
+  }
+}
+
+impl TwoSquare {
+  pub fn new(
+    alphabet: Alphabet,
+    merge: Option<(char, char)>,
+    filler: char,
+    orientation: Orientation,
+  ) -> Self {
+    TwoSquare {
+      alphabet,
+      merge,
+      filler,
+      orientation,
+    }
+  }
+
+  /// The squares a pair is read from and the squares its replacement comes
+  /// from, in the order `digraphic::cross` takes them.
+  fn squares<'a>(
+    &self,
+    key: &'a TwoSquareKey,
+  ) -> (
+    (&'a PolybiusSquare, &'a PolybiusSquare),
+    (&'a PolybiusSquare, &'a PolybiusSquare),
+  ) {
+    match self.orientation {
+      Orientation::Horizontal => ((&key.0, &key.1), (&key.1, &key.0)),
+      Orientation::Vertical => ((&key.0, &key.1), (&key.0, &key.1)),
+    }
+  }
+
+  pub fn random_key<R: Rng>(
+    &self,
+    rng: &mut R,
+  ) -> Result<TwoSquareKey, ParseError> {
+    Ok(TwoSquareKey::new(
+      digraphic::random_square(&self.alphabet, self.merge, rng)?,
+      digraphic::random_square(&self.alphabet, self.merge, rng)?,
+    ))
+  }
+
+  /// Improves `key` by rearranging the letters of its squares, keeping
+  /// changes that lower the confidence score of the deciphered text.
+  pub fn hill_climb<R: Rng>(
+    &self,
+    ciphertext: &str,
+    key: TwoSquareKey,
+    get_confidence: &GetConfidence,
+    options: &AnnealOptions,
+    rng: &mut R,
+  ) -> (TwoSquareKey, f64) {
+    let ((first, second), confidence) = digraphic::anneal_squares(
+      (key.0, key.1),
+      get_confidence,
+      options,
+      rng,
+      |(first, second)| {
+        let key = TwoSquareKey::new(first.clone(), second.clone());
+        self.decipher(ciphertext, &key)
+      },
+    );
+
+    (TwoSquareKey::new(first, second), confidence)
+  }
+}
+
+impl Encipher for TwoSquare {
+  type Key = TwoSquareKey;
+
+  fn encipher(&self, plaintext: &str, key: &Self::Key) -> String {
+    let letters = digraphic::letters(plaintext, &key.0, self.filler);
+    let (from, to) = self.squares(key);
+
+    digraphic::cross(&letters, from, to)
+  }
+}
+
+impl Decipher for TwoSquare {
+  type Key = TwoSquareKey;
+
+  fn decipher(&self, ciphertext: &str, key: &Self::Key) -> String {
+    let letters = digraphic::letters(ciphertext, &key.0, self.filler);
+    let (from, to) = self.squares(key);
+
+    // Crossing is its own inverse once the squares swap roles.
+    digraphic::cross(&letters, to, from)
+  }
+}
+
+#[cfg(test)]
+mod tests;
@@ -1,6 +1,10 @@
+mod solve;
+
 use crate::{
   alphabet::Alphabet,
   cipher::{Decipher, Encipher},
+  language::Language,
+  periodicity::{self, KeyLengthCandidate},
 };
 use cipher_derive::{IntoDecipherKey, IntoEncipherKey};
 use serde::{Deserialize, Serialize};
@@ -68,6 +72,36 @@ impl Vigenere {
       skip_whitespace,
     }
   }
+
+  pub fn key_lengths(
+    &self,
+    ciphertext: &str,
+    language: &Language,
+    max_key_length: usize,
+  ) -> Vec<KeyLengthCandidate> {
+    let stream =
+      periodicity::key_stream(ciphertext, &self.alphabet, self.skip_whitespace);
+    periodicity::rank_key_lengths(
+      &stream,
+      self.alphabet.len(),
+      language.index_of_coincidence(),
+      max_key_length,
+    )
+  }
+
+  pub fn friedman_estimate(
+    &self,
+    ciphertext: &str,
+    language: &Language,
+  ) -> Option<f64> {
+    let stream =
+      periodicity::key_stream(ciphertext, &self.alphabet, self.skip_whitespace);
+    periodicity::friedman_estimate(
+      &stream,
+      self.alphabet.len(),
+      language.index_of_coincidence(),
+    )
+  }
 }
 
 impl std::fmt::Display for VigenereKey {
@@ -0,0 +1,84 @@
+use super::{Vigenere, VigenereKey};
+use crate::cipher::{Decipher, KeysIterator, caesar::Caesar};
+use crate::language::{GetConfidence, Language};
+use crate::search::{self, AnnealOptions};
+use rand::{Rng, RngExt};
+
+impl Vigenere {
+  /// Splits the ciphertext into `key_length` cosets and solves each one as a
+  /// Caesar cipher against the unigram statistics of `language`.
+  pub fn frequency_key(
+    &self,
+    ciphertext: &str,
+    key_length: usize,
+    language: &Language,
+  ) -> VigenereKey {
+    let letters: Vec<char> = ciphertext
+      .chars()
+      .filter(|c| !(c.is_whitespace() && self.skip_whitespace))
+      .collect();
+    let caesar = Caesar::new(self.alphabet.clone());
+
+    let key = (0..key_length)
+      .map(|offset| {
+        let coset: String = letters
+          .iter()
+          .skip(offset)
+          .step_by(key_length)
+          .filter(|&&c| self.alphabet.contains(c))
+          .collect();
+
+        caesar
+          .keys_iter()
+          .map(|key| {
+            let confidence = language
+              .text_confidence_chi2_unigram(&caesar.decipher(&coset, &key));
+            (key, confidence)
+          })
+          .min_by(|a, b| {
+            a.1.partial_cmp(&b.1).unwrap_or(std::cmp::Ordering::Equal)
+          })
+          .map(|(key, _)| key.to_string())
+          .unwrap_or_default()
+      })
+      .collect();
+
+    VigenereKey::new(key)
+  }
+
+  /// Improves `key` by changing one key letter at a time, keeping changes
+  /// that lower the confidence score of the deciphered text.
+  pub fn hill_climb<R: Rng>(
+    &self,
+    ciphertext: &str,
+    key: VigenereKey,
+    get_confidence: &GetConfidence,
+    options: &AnnealOptions,
+    rng: &mut R,
+  ) -> (VigenereKey, f64) {
+    if key.is_empty() {
+      let confidence = get_confidence.run(ciphertext);
+      return (key, confidence);
+    }
+
+    let letters: Vec<_> = key.0.chars().collect();
+    let (letters, confidence) = search::anneal(
+      letters,
+      options,
+      rng,
+      |letters, rng| {
+        let mut letters = letters.clone();
+        let i = rng.random_range(0..letters.len());
+        let k = rng.random_range(0..self.alphabet.len());
+        letters[i] = self.alphabet.get(k).unwrap_or(letters[i]);
+        letters
+      },
+      |letters| {
+        let key = VigenereKey::new(letters.iter().collect());
+        get_confidence.run(&self.decipher(ciphertext, &key))
+      },
+    );
+
+    (VigenereKey::new(letters.into_iter().collect()), confidence)
+  }
+}
@@ -38,3 +38,54 @@ cipher_test!(
   KEY_A,
   skip_whitespace
 );
+
+#[test]
+fn key_lengths() {
+  use crate::language::Language;
+  use crate::ngrams::RankedNgrams;
+  use ahash::AHashMap;
+
+  let language =
+    Language::new(RankedNgrams::default(), AHashMap::new(), 0.0667);
+  let key = VigenereKey::new("lemon".to_string());
+
+  for context in [&*CONTEXT_INCLUDE_WHITESPACE, &*CONTEXT_SKIP_WHITESPACE] {
+    let ciphertext = context.encipher(&A_TALE_OF_TWO_CITIES, &key);
+    let candidates = context.key_lengths(&ciphertext, &language, 20);
+
+    assert_eq!(candidates[0].key_length, 5);
+  }
+}
+
+#[test]
+fn friedman_estimate() {
+  use crate::language::Language;
+  use crate::ngrams::RankedNgrams;
+  use ahash::AHashMap;
+
+  let language =
+    Language::new(RankedNgrams::default(), AHashMap::new(), 0.0667);
+  let context = &*CONTEXT_SKIP_WHITESPACE;
+
+  assert!(context.friedman_estimate("", &language).is_none());
+  assert!(
+    context
+      .friedman_estimate("abcdefghijklmnopqrstuvwxyz", &language)
+      .is_none()
+  );
+  assert_eq!(context.friedman_estimate("aaaa", &language), Some(1.0));
+
+  let ciphertext = context.encipher(&A_TALE_OF_TWO_CITIES, &KEY_A);
+  let estimate = context.friedman_estimate(&ciphertext, &language).unwrap();
+  assert!((2.0..5.0).contains(&estimate), "{estimate}");
+}
+
+#[test]
+fn frequency_key() {
+  let key = VigenereKey::new("lemon".to_string());
+  let ciphertext =
+    CONTEXT_SKIP_WHITESPACE.encipher(&A_TALE_OF_TWO_CITIES, &key);
+
+  let solved = CONTEXT_SKIP_WHITESPACE.frequency_key(&ciphertext, 5, &ENGLISH);
+  assert_eq!(solved.to_string(), "lemon");
+}
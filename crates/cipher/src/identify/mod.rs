@@ -0,0 +1,153 @@
+use crate::alphabet::Alphabet;
+use crate::cipher::{Decipher, KeysIterator, caesar::Caesar};
+use crate::language::Language;
+use crate::periodicity;
+use std::fmt;
+
+// Periods tried when looking for a polyalphabetic key.
+const MAX_PERIOD: usize = 15;
+
+// Unigram chi² below which letter frequencies look like the language.
+const CHI2_LANGUAGE_LIKE: f64 = 1.0;
+
+// Penalty for calling a text Caesar when its best shift is the identity.
+const IDENTITY_SHIFT_PENALTY: f64 = 0.25;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CipherType {
+  Caesar,
+  Substitution,
+  Vigenere,
+  Autokey,
+  Transposition,
+}
+
+#[derive(Debug, Clone)]
+pub struct Features {
+  pub letters: usize,
+  pub symbols: usize,
+  pub doubled_letters: usize,
+  pub index_of_coincidence: f64,
+  /// The most likely period of a polyalphabetic key, up to 15.
+  pub period: usize,
+  pub periodic_index_of_coincidence: f64,
+  pub chi2: f64,
+  pub best_shift_chi2: f64,
+  best_shift_is_identity: bool,
+}
+
+impl fmt::Display for CipherType {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    match self {
+      CipherType::Caesar => write!(f, "Caesar"),
+      CipherType::Substitution => write!(f, "Substitution"),
+      CipherType::Vigenere => write!(f, "Vigenère"),
+      CipherType::Autokey => write!(f, "Autokey"),
+      CipherType::Transposition => write!(f, "Transposition"),
+    }
+  }
+}
+
+impl Features {
+  pub fn new(
+    ciphertext: &str,
+    alphabet: &Alphabet,
+    language: &Language,
+  ) -> Self {
+    let stream = periodicity::key_stream(ciphertext, alphabet, true);
+    let letters: Vec<usize> = stream.iter().flatten().copied().collect();
+    let text: String =
+      letters.iter().filter_map(|&i| alphabet.get(i)).collect();
+
+    let mut symbols: Vec<char> =
+      ciphertext.chars().filter(|c| !c.is_whitespace()).collect();
+    symbols.sort();
+    symbols.dedup();
+
+    // A period of 1 is the whole text, which says nothing new.
+    let (period, periodic_index_of_coincidence) =
+      periodicity::rank_key_lengths(
+        &stream,
+        alphabet.len(),
+        language.index_of_coincidence(),
+        MAX_PERIOD,
+      )
+      .into_iter()
+      .find(|candidate| candidate.key_length > 1)
+      .map(|candidate| (candidate.key_length, candidate.index_of_coincidence))
+      .unwrap_or((1, 0.0));
+
+    let caesar = Caesar::new(alphabet.clone());
+    let (best_shift, best_shift_chi2) = caesar
+      .keys_iter()
+      .map(|key| {
+        let chi2 =
+          language.text_confidence_chi2_unigram(&caesar.decipher(&text, &key));
+        (key.to_string(), chi2)
+      })
+      .fold((String::new(), f64::MAX), |best, x| {
+        if x.1 < best.1 { x } else { best }
+      });
+
+    Features {
+      letters: letters.len(),
+      symbols: symbols.len(),
+      doubled_letters: letters.windows(2).filter(|w| w[0] == w[1]).count(),
+      index_of_coincidence: periodicity::index_of_coincidence(
+        letters.iter().copied(),
+        alphabet.len(),
+      ),
+      periodic_index_of_coincidence,
+      period,
+      chi2: language.text_confidence_chi2_unigram(&text),
+      best_shift_chi2,
+      best_shift_is_identity: alphabet.get(0).map(|c| c.to_string())
+        == Some(best_shift),
+    }
+  }
+
+  /// Ranks the cipher types by how well the features fit them, best first.
+  /// Scores are lower for a better fit, as with the confidence functions.
+  pub fn rank(
+    &self,
+    alphabet: &Alphabet,
+    language: &Language,
+  ) -> Vec<(CipherType, f64)> {
+    let random_ioc = 1.0 / alphabet.len() as f64;
+    let language_ioc = language.index_of_coincidence();
+    let distance = |ioc: f64| {
+      ((ioc - language_ioc).abs() / (language_ioc - random_ioc).abs()).min(1.0)
+    };
+
+    // 0 when the text is monoalphabetic, 1 when it is flat.
+    let mono = distance(self.index_of_coincidence);
+    // 1 when splitting the text by the period makes it monoalphabetic.
+    let periodic = ((self.periodic_index_of_coincidence
+      - self.index_of_coincidence)
+      / (language_ioc - random_ioc))
+      .clamp(0.0, 1.0);
+    let plain = self.chi2 / CHI2_LANGUAGE_LIKE;
+    let shift = self.best_shift_chi2 / CHI2_LANGUAGE_LIKE;
+    let identity_penalty = if self.best_shift_is_identity {
+      IDENTITY_SHIFT_PENALTY
+    } else {
+      0.0
+    };
+
+    let mut ranking = vec![
+      (CipherType::Transposition, mono + plain),
+      (CipherType::Caesar, mono + shift + identity_penalty),
+      (CipherType::Substitution, mono + (1.0 - shift).max(0.0)),
+      (CipherType::Vigenere, (1.0 - mono) + (1.0 - periodic)),
+      (CipherType::Autokey, (1.0 - mono) + periodic),
+    ];
+    ranking.sort_by(|a, b| {
+      a.1.partial_cmp(&b.1).unwrap_or(std::cmp::Ordering::Equal)
+    });
+
+    ranking
+  }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,64 @@
+use super::*;
+use crate::cipher::{
+  Encipher, autokey::Autokey, autokey::AutokeyKey, caesar::CaesarKey,
+  substitution::Substitution, substitution::SubstitutionEncipherKey,
+  vigenere::Vigenere, vigenere::VigenereKey,
+};
+use crate::tests::*;
+
+fn identify(ciphertext: &str) -> CipherType {
+  let alphabet = Alphabet::latin();
+  let features = Features::new(ciphertext, &alphabet, &ENGLISH);
+
+  features.rank(&alphabet, &ENGLISH)[0].0
+}
+
+#[test]
+fn transposition() {
+  let mut letters: Vec<char> = A_TALE_OF_TWO_CITIES
+    .chars()
+    .filter(|c| !c.is_whitespace())
+    .collect();
+  letters.reverse();
+  let ciphertext: String = letters.into_iter().collect();
+
+  assert_eq!(identify(&ciphertext), CipherType::Transposition);
+}
+
+#[test]
+fn caesar() {
+  let caesar = Caesar::new(Alphabet::latin());
+  let ciphertext = caesar.encipher(&A_TALE_OF_TWO_CITIES, &CaesarKey::new('h'));
+
+  assert_eq!(identify(&ciphertext), CipherType::Caesar);
+}
+
+#[test]
+fn substitution() {
+  let substitution = Substitution::new(Alphabet::latin());
+  let key = SubstitutionEncipherKey::new(
+    "qwertyuiopasdfghjklzxcvbnm".to_string(),
+    &Alphabet::latin(),
+  );
+  let ciphertext = substitution.encipher(&A_TALE_OF_TWO_CITIES, &key);
+
+  assert_eq!(identify(&ciphertext), CipherType::Substitution);
+}
+
+#[test]
+fn vigenere() {
+  let vigenere = Vigenere::new(Alphabet::latin(), true);
+  let key = VigenereKey::new("lemon".to_string());
+  let ciphertext = vigenere.encipher(&A_TALE_OF_TWO_CITIES, &key);
+
+  assert_eq!(identify(&ciphertext), CipherType::Vigenere);
+}
+
+#[test]
+fn autokey() {
+  let autokey = Autokey::new(Alphabet::latin(), true);
+  let key = AutokeyKey::new("queen".to_string());
+  let ciphertext = autokey.encipher(&A_TALE_OF_TWO_CITIES, &key);
+
+  assert_eq!(identify(&ciphertext), CipherType::Autokey);
+}
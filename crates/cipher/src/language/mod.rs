@@ -1,9 +1,15 @@
-use crate::ngrams::{Ngrams, RankedNgrams};
+use crate::alphabet::Alphabet;
+use crate::ngrams::{LogNgramTable, Ngrams, RankedNgrams};
 use crate::resources;
+use crate::words::WordModel;
 use ahash::{AHashMap, AHashSet};
 use serde::{Deserialize, Serialize};
+use std::fmt;
 use std::sync::Arc;
 
+// Prefix identifying the compact binary encoding of a language model.
+const BINARY_MAGIC: &[u8; 4] = b"CTLM";
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(from = "LanguageSerializer", into = "LanguageSerializer")]
 pub struct Language {
@@ -22,6 +28,21 @@ pub struct LanguageSerializer {
   index_of_coincidence: f64,
 }
 
+#[derive(Debug)]
+pub enum ParseError {
+  Json(serde_json::Error),
+  Binary(bincode::error::DecodeError),
+}
+
+impl fmt::Display for ParseError {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    match self {
+      ParseError::Json(e) => write!(f, "Invalid language JSON: {e}"),
+      ParseError::Binary(e) => write!(f, "Invalid binary language: {e}"),
+    }
+  }
+}
+
 impl From<Language> for LanguageSerializer {
   fn from(language: Language) -> Self {
     LanguageSerializer {
@@ -78,6 +99,71 @@ impl Language {
       .expect("Failed to parse resources::ENGLISH")
   }
 
+  pub fn russian() -> Self {
+    serde_json::from_str(resources::RUSSIAN)
+      .expect("Failed to parse resources::RUSSIAN")
+  }
+
+  pub fn greek() -> Self {
+    serde_json::from_str(resources::GREEK)
+      .expect("Failed to parse resources::GREEK")
+  }
+
+  /// Parses a language model in either the binary encoding produced by
+  /// `to_binary` or JSON.
+  pub fn from_bytes(bytes: &[u8]) -> Result<Self, ParseError> {
+    match bytes.strip_prefix(BINARY_MAGIC) {
+      Some(binary) => {
+        bincode::serde::decode_from_slice(binary, bincode::config::standard())
+          .map(|(language, _)| language)
+          .map_err(ParseError::Binary)
+      }
+      None => serde_json::from_slice(bytes).map_err(ParseError::Json),
+    }
+  }
+
+  pub fn to_binary(&self) -> Vec<u8> {
+    let mut bytes = BINARY_MAGIC.to_vec();
+    bytes.extend(
+      bincode::serde::encode_to_vec(self, bincode::config::standard())
+        .expect("Failed to encode language"),
+    );
+    bytes
+  }
+
+  pub fn index_of_coincidence(&self) -> f64 {
+    self.index_of_coincidence
+  }
+
+  pub fn char_ngrams(&self, n: usize) -> Option<&RankedNgrams> {
+    self.char_ngrams.get(&n)
+  }
+
+  /// The letters the language has unigram statistics for.
+  pub fn alphabet(&self) -> Alphabet {
+    let mut letters: Vec<char> = self
+      .char_ngrams
+      .get(&1)
+      .into_iter()
+      .flat_map(|unigrams| unigrams.ngrams())
+      .filter_map(|ngram| ngram.chars().next())
+      .collect();
+    letters.sort();
+
+    Alphabet::from_iter(letters)
+  }
+
+  pub fn log_ngram_table(&self, n: usize) -> Option<LogNgramTable> {
+    self
+      .char_ngrams
+      .get(&n)
+      .map(|ngrams| LogNgramTable::new(ngrams, n, self.alphabet()))
+  }
+
+  pub fn word_model(&self) -> WordModel {
+    WordModel::new(&self.word_ngrams)
+  }
+
   pub fn text_confidence_chi2_unigram(&self, text: &str) -> f64 {
     self.text_confidence_chi2_ngram(text, 1)
   }
@@ -136,3 +222,6 @@ impl Language {
     chi2_sum
   }
 }
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,107 @@
+use super::*;
+use crate::tests::*;
+
+#[test]
+fn binary_round_trip() {
+  let binary = ENGLISH.to_binary();
+  let language = Language::from_bytes(&binary).unwrap();
+
+  for n in [1, 2, 3] {
+    assert_eq!(
+      language
+        .log_ngram_table(n)
+        .unwrap()
+        .text_confidence(&QUICK_BROWN_FOX),
+      ENGLISH
+        .log_ngram_table(n)
+        .unwrap()
+        .text_confidence(&QUICK_BROWN_FOX)
+    );
+  }
+}
+
+#[test]
+fn from_bytes_json() {
+  let language = Language::from_bytes(resources::ENGLISH.as_bytes()).unwrap();
+
+  assert_eq!(
+    language.index_of_coincidence(),
+    ENGLISH.index_of_coincidence()
+  );
+  assert!(matches!(
+    Language::from_bytes(b"CTLM\xff"),
+    Err(ParseError::Binary(_))
+  ));
+  assert!(matches!(
+    Language::from_bytes(b"{"),
+    Err(ParseError::Json(_))
+  ));
+}
+
+#[test]
+fn log_ngram_table_higher_orders() {
+  use crate::alphabet::Alphabet;
+  use rand::{SeedableRng, rngs::StdRng, seq::SliceRandom};
+
+  // The bundled models stop at trigrams, so count those of the tale.
+  let letters: Vec<char> = A_TALE_OF_TWO_CITIES
+    .chars()
+    .filter(char::is_ascii_lowercase)
+    .collect();
+  let mut shuffled = letters.clone();
+  shuffled.shuffle(&mut StdRng::seed_from_u64(0));
+  let shuffled: String = shuffled.into_iter().collect();
+
+  for n in [4, 5] {
+    let mut counts: AHashMap<String, u64> = AHashMap::new();
+    for window in letters.windows(n) {
+      *counts.entry(window.iter().collect()).or_insert(0) += 1;
+    }
+    let table = LogNgramTable::new(
+      &RankedNgrams::from_iter(counts),
+      n,
+      Alphabet::latin(),
+    );
+
+    assert!(
+      table.text_confidence(&A_TALE_OF_TWO_CITIES)
+        < table.text_confidence(&shuffled)
+    );
+  }
+}
+
+#[test]
+fn bundled_languages_rank_plaintext_first() {
+  use crate::alphabet::Alphabet;
+  use crate::cipher::Encipher;
+  use crate::cipher::caesar::{Caesar, CaesarKey};
+
+  for (language, alphabet, plaintext) in [
+    (Language::russian(), Alphabet::russian(), &*ANNA_KARENINA),
+    (
+      Language::greek(),
+      Alphabet::greek(),
+      &*HUMAN_RIGHTS_ARTICLE_1,
+    ),
+  ] {
+    let caesar = Caesar::new(alphabet.clone());
+    let table = language.log_ngram_table(3).unwrap();
+    let word_model = language.word_model();
+
+    for shift in alphabet.iter().skip(1) {
+      let shifted = caesar.encipher(plaintext, &CaesarKey::new(shift));
+
+      assert!(
+        language.text_confidence_chi2_bigram(plaintext)
+          < language.text_confidence_chi2_bigram(&shifted)
+      );
+      assert!(
+        table.text_confidence(plaintext) < table.text_confidence(&shifted)
+      );
+      assert!(
+        word_model.text_confidence(plaintext)
+          < word_model.text_confidence(&shifted)
+      );
+    }
+  }
+}
@@ -1,8 +1,13 @@
 pub mod alphabet;
 pub mod cipher;
+pub mod identify;
 pub mod language;
 pub mod ngrams;
+pub mod periodicity;
+pub mod polybius;
 pub mod resources;
+pub mod search;
+pub mod words;
 
 #[cfg(test)]
 mod tests;
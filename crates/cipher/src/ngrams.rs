@@ -1,3 +1,4 @@
+use crate::alphabet::Alphabet;
 use ahash::AHashMap;
 
 use serde::{Deserialize, Serialize};
@@ -134,3 +135,72 @@ impl RankedNgrams {
     Ngrams::from_text(text, ngrams).into()
   }
 }
+
+/// Log probabilities of every n-gram over an alphabet, stored densely and
+/// indexed by the base `alphabet.len()` number formed from the letter
+/// positions. N-grams missing from the model score `floor`.
+#[derive(Debug, Clone)]
+pub struct LogNgramTable {
+  n: usize,
+  alphabet: Alphabet,
+  log_probabilities: Vec<f32>,
+}
+
+impl LogNgramTable {
+  pub fn new(ngrams: &RankedNgrams, n: usize, alphabet: Alphabet) -> Self {
+    let size = alphabet.len().pow(n as u32);
+    let min_probability = ngrams
+      .iter()
+      .map(|(_, p)| *p)
+      .filter(|p| *p > 0.0)
+      .fold(1.0, f64::min);
+    let floor = (min_probability / 100.0).ln() as f32;
+
+    let mut log_probabilities = vec![floor; size];
+    for (ngram, probability) in ngrams.iter() {
+      let index = ngram.chars().try_fold(0, |acc, c| {
+        alphabet.get_index(c).map(|i| acc * alphabet.len() + i)
+      });
+      if let Some(index) = index.filter(|_| ngram.chars().count() == n) {
+        log_probabilities[index] = probability.ln() as f32;
+      }
+    }
+
+    LogNgramTable {
+      n,
+      alphabet,
+      log_probabilities,
+    }
+  }
+
+  /// Negative mean log probability of the n-grams in `text`, so that lower
+  /// is better as with the chi² confidences. N-grams never span characters
+  /// outside the alphabet, such as the spaces between words.
+  pub fn text_confidence(&self, text: &str) -> f64 {
+    let size = self.log_probabilities.len();
+    let mut index = 0;
+    let mut run = 0;
+    let mut sum = 0.0;
+    let mut count = 0;
+
+    for c in text.chars() {
+      let Some(i) = self.alphabet.get_index(c) else {
+        run = 0;
+        continue;
+      };
+
+      index = (index * self.alphabet.len() + i) % size;
+      run += 1;
+      if run >= self.n {
+        sum += self.log_probabilities[index] as f64;
+        count += 1;
+      }
+    }
+
+    if count == 0 {
+      return f64::MAX;
+    }
+
+    -sum / count as f64
+  }
+}
@@ -0,0 +1,203 @@
+use crate::alphabet::Alphabet;
+use ahash::AHashMap;
+
+// Length of the repeated sequences searched for by the Kasiski examination.
+const KASISKI_SEQUENCE_LEN: usize = 3;
+
+#[derive(Debug, Clone)]
+pub struct KeyLengthCandidate {
+  pub key_length: usize,
+  pub index_of_coincidence: f64,
+  pub kasiski: f64,
+  pub score: f64,
+}
+
+#[derive(Debug, Clone)]
+pub struct PeriodCandidate {
+  pub period: usize,
+  pub index_of_coincidence: f64,
+  pub score: f64,
+}
+
+/// Maps every character of `text` that advances a periodic key to its
+/// alphabet index, or `None` if the character is not in the alphabet.
+/// Whitespace only advances the key when `skip_whitespace` is false.
+pub fn key_stream(
+  text: &str,
+  alphabet: &Alphabet,
+  skip_whitespace: bool,
+) -> Vec<Option<usize>> {
+  text
+    .chars()
+    .filter(|c| !(c.is_whitespace() && skip_whitespace))
+    .map(|c| alphabet.get_index(c))
+    .collect()
+}
+
+pub fn index_of_coincidence(
+  letters: impl Iterator<Item = usize>,
+  alphabet_len: usize,
+) -> f64 {
+  let mut counts = vec![0usize; alphabet_len];
+  let mut total = 0;
+  for i in letters {
+    counts[i] += 1;
+    total += 1;
+  }
+
+  if total < 2 {
+    return 0.0;
+  }
+
+  let pairs: usize = counts.iter().map(|n| n * n.saturating_sub(1)).sum();
+  pairs as f64 / (total * (total - 1)) as f64
+}
+
+/// Average index of coincidence of the cosets obtained by splitting the key
+/// stream into `period` columns.
+pub fn periodic_index_of_coincidence(
+  stream: &[Option<usize>],
+  period: usize,
+  alphabet_len: usize,
+) -> f64 {
+  let sum: f64 = (0..period)
+    .map(|offset| {
+      let coset = stream.iter().skip(offset).step_by(period).flatten();
+      index_of_coincidence(coset.copied(), alphabet_len)
+    })
+    .sum();
+
+  sum / period as f64
+}
+
+/// Distances between consecutive occurrences of every repeated sequence of
+/// letters in the key stream.
+pub fn kasiski_spacings(stream: &[Option<usize>]) -> Vec<usize> {
+  let mut positions: AHashMap<&[Option<usize>], usize> = AHashMap::new();
+  let mut spacings = Vec::new();
+
+  for (i, window) in stream.windows(KASISKI_SEQUENCE_LEN).enumerate() {
+    if window.iter().any(|x| x.is_none()) {
+      continue;
+    }
+    if let Some(previous) = positions.insert(window, i) {
+      spacings.push(i - previous);
+    }
+  }
+
+  spacings
+}
+
+/// Friedman's estimate of the key length from the index of coincidence of
+/// the whole ciphertext, at least 1. None without letters, or when their
+/// index of coincidence is no higher than that of random text.
+pub fn friedman_estimate(
+  stream: &[Option<usize>],
+  alphabet_len: usize,
+  language_ioc: f64,
+) -> Option<f64> {
+  let ioc =
+    index_of_coincidence(stream.iter().flatten().copied(), alphabet_len);
+  let random_ioc = 1.0 / alphabet_len as f64;
+  if ioc <= random_ioc {
+    return None;
+  }
+
+  Some(((language_ioc - random_ioc) / (ioc - random_ioc)).max(1.0))
+}
+
+/// Ranks every key length up to `max_key_length`, best first. A key length
+/// scores well (low) when its cosets have an index of coincidence close to
+/// that of the language, and better still when it divides many of the
+/// Kasiski spacings.
+pub fn rank_key_lengths(
+  stream: &[Option<usize>],
+  alphabet_len: usize,
+  language_ioc: f64,
+  max_key_length: usize,
+) -> Vec<KeyLengthCandidate> {
+  let random_ioc = 1.0 / alphabet_len as f64;
+  let spacings = kasiski_spacings(stream);
+
+  let mut candidates: Vec<_> = (1..=max_key_length)
+    .map(|key_length| {
+      let index_of_coincidence =
+        periodic_index_of_coincidence(stream, key_length, alphabet_len);
+      let kasiski = if spacings.is_empty() {
+        0.0
+      } else {
+        let divisible =
+          spacings.iter().filter(|s| *s % key_length == 0).count();
+        divisible as f64 / spacings.len() as f64
+      };
+
+      let distance = (index_of_coincidence - language_ioc).abs()
+        / (language_ioc - random_ioc).abs();
+      let score = distance * (1.0 - kasiski / 2.0);
+
+      KeyLengthCandidate {
+        key_length,
+        index_of_coincidence,
+        kasiski,
+        score,
+      }
+    })
+    .collect();
+
+  candidates.sort_by(|a, b| {
+    a.score
+      .partial_cmp(&b.score)
+      .unwrap_or(std::cmp::Ordering::Equal)
+  });
+
+  candidates
+}
+
+/// Ranks the periods from 2 to `max_period` of a fractionating cipher that
+/// splits letters into `dimensions` coordinates, best first. Within a
+/// block, letters `period / dimensions` places apart (rounded up) are built
+/// from overlapping plaintext letters, so at the right period such pairs
+/// repeat more often than the same pairs taken across block boundaries.
+/// The score is the ratio of the two indices of coincidence.
+pub fn rank_fractionation_periods(
+  letters: &[usize],
+  alphabet_len: usize,
+  dimensions: usize,
+  max_period: usize,
+) -> Vec<PeriodCandidate> {
+  let pair = |(a, b): (&usize, &usize)| a * alphabet_len + b;
+
+  let mut candidates: Vec<_> = (2..=max_period)
+    .map(|period| {
+      let step = period.div_ceil(dimensions);
+      let within = letters
+        .chunks_exact(period)
+        .flat_map(|block| block.iter().zip(&block[step..]))
+        .map(pair);
+      let within = index_of_coincidence(within, alphabet_len * alphabet_len);
+      let baseline = index_of_coincidence(
+        letters.iter().zip(letters.iter().skip(step)).map(pair),
+        alphabet_len * alphabet_len,
+      );
+      let score = if baseline > 0.0 {
+        within / baseline
+      } else {
+        0.0
+      };
+
+      PeriodCandidate {
+        period,
+        index_of_coincidence: within,
+        score,
+      }
+    })
+    .collect();
+
+  candidates.sort_by(|a, b| {
+    b.score
+      .partial_cmp(&a.score)
+      .unwrap_or(std::cmp::Ordering::Equal)
+  });
+
+  candidates
+}
@@ -0,0 +1,159 @@
+use crate::alphabet::Alphabet;
+use ahash::AHashMap;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// A square grid holding every letter of an alphabet once, as used by
+/// Playfair and its relatives. Alphabets one letter too long for a square,
+/// like Latin, merge one letter into another (J into I).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(try_from = "SerializedSquare", into = "SerializedSquare")]
+pub struct PolybiusSquare {
+  letters: Vec<char>,
+  side: usize,
+  merge: Option<(char, char)>,
+  positions: AHashMap<char, (usize, usize)>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct SerializedSquare {
+  letters: String,
+  merge: Option<(char, char)>,
+}
+
+impl TryFrom<SerializedSquare> for PolybiusSquare {
+  type Error = ParseError;
+
+  fn try_from(value: SerializedSquare) -> Result<Self, Self::Error> {
+    PolybiusSquare::from_letters(value.letters.chars().collect(), value.merge)
+  }
+}
+
+impl From<PolybiusSquare> for SerializedSquare {
+  fn from(value: PolybiusSquare) -> Self {
+    SerializedSquare {
+      letters: value.letters.into_iter().collect(),
+      merge: value.merge,
+    }
+  }
+}
+
+#[derive(Debug)]
+pub enum ParseError {
+  InvalidChar(char),
+  NotSquare(usize),
+  RepeatedChar(char),
+}
+
+impl fmt::Display for ParseError {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    match self {
+      ParseError::InvalidChar(c) => write!(f, "Invalid character: '{c}'"),
+      ParseError::NotSquare(len) => {
+        write!(f, "{len} letters do not fill a square")
+      }
+      ParseError::RepeatedChar(c) => write!(f, "Repeated character: '{c}'"),
+    }
+  }
+}
+
+impl PolybiusSquare {
+  /// Fills the square with the letters of `keyword`, then the rest of
+  /// `alphabet`, skipping repeated letters. `merge` maps a letter onto the
+  /// one that stands in for it in the square.
+  pub fn new(
+    keyword: &str,
+    alphabet: &Alphabet,
+    merge: Option<(char, char)>,
+  ) -> Result<Self, ParseError> {
+    if let Some(c) = keyword.chars().find(|&c| !alphabet.contains(c)) {
+      return Err(ParseError::InvalidChar(c));
+    }
+
+    let mut letters: Vec<char> = Vec::with_capacity(alphabet.len());
+    keyword
+      .chars()
+      .chain(alphabet.iter())
+      .map(|c| merged(merge, c))
+      .for_each(|c| {
+        if !letters.contains(&c) {
+          letters.push(c);
+        }
+      });
+
+    Self::from_letters(letters, merge)
+  }
+
+  /// Builds a square from its letters in reading order, each of which may
+  /// appear only once.
+  pub fn from_letters(
+    letters: Vec<char>,
+    merge: Option<(char, char)>,
+  ) -> Result<Self, ParseError> {
+    let side = (letters.len() as f64).sqrt().round() as usize;
+    if side * side != letters.len() {
+      return Err(ParseError::NotSquare(letters.len()));
+    }
+
+    let mut positions = AHashMap::with_capacity(letters.len());
+    for (i, &c) in letters.iter().enumerate() {
+      if positions.insert(c, (i / side, i % side)).is_some() {
+        return Err(ParseError::RepeatedChar(c));
+      }
+    }
+
+    Ok(PolybiusSquare {
+      letters,
+      side,
+      merge,
+      positions,
+    })
+  }
+
+  pub fn side(&self) -> usize {
+    self.side
+  }
+
+  pub fn letters(&self) -> &[char] {
+    &self.letters
+  }
+
+  pub fn merge(&self) -> Option<(char, char)> {
+    self.merge
+  }
+
+  /// Maps a merged letter onto the letter standing in for it.
+  pub fn normalize(&self, c: char) -> char {
+    merged(self.merge, c)
+  }
+
+  pub fn contains(&self, c: char) -> bool {
+    self.position(c).is_some()
+  }
+
+  /// Row and column of `c`, after merging.
+  pub fn position(&self, c: char) -> Option<(usize, usize)> {
+    self.positions.get(&self.normalize(c)).copied()
+  }
+
+  pub fn get(&self, row: usize, column: usize) -> Option<char> {
+    if row >= self.side || column >= self.side {
+      return None;
+    }
+
+    self.letters.get(row * self.side + column).copied()
+  }
+}
+
+fn merged(merge: Option<(char, char)>, c: char) -> char {
+  match merge {
+    Some((from, to)) if c == from => to,
+    _ => c,
+  }
+}
+
+impl fmt::Display for PolybiusSquare {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(f, "{}", self.letters.iter().collect::<String>())
+  }
+}
@@ -1 +1,3 @@
 pub static ENGLISH: &str = include_str!("../resources/languages/english.json");
+pub static RUSSIAN: &str = include_str!("../resources/languages/russian.json");
+pub static GREEK: &str = include_str!("../resources/languages/greek.json");
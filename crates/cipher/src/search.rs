@@ -0,0 +1,60 @@
+use rand::{Rng, RngExt};
+
+#[derive(Debug, Clone)]
+pub struct AnnealOptions {
+  pub iterations: usize,
+  pub temperature: f64,
+}
+
+impl Default for AnnealOptions {
+  fn default() -> Self {
+    AnnealOptions {
+      iterations: 5000,
+      temperature: 0.0,
+    }
+  }
+}
+
+/// Minimises `score` starting from `initial`, moving to a random neighbour
+/// on every iteration. Worse neighbours are accepted with the usual
+/// Metropolis probability while the temperature, which cools linearly to
+/// zero, is positive; a temperature of zero is plain hill climbing.
+///
+/// Returns the best key seen together with its score.
+pub fn anneal<K, R>(
+  initial: K,
+  options: &AnnealOptions,
+  rng: &mut R,
+  mut neighbour: impl FnMut(&K, &mut R) -> K,
+  mut score: impl FnMut(&K) -> f64,
+) -> (K, f64)
+where
+  K: Clone,
+  R: Rng,
+{
+  let mut current_score = score(&initial);
+  let mut current = initial;
+  let mut best = (current.clone(), current_score);
+
+  for i in 0..options.iterations {
+    let candidate = neighbour(&current, rng);
+    let candidate_score = score(&candidate);
+    let delta = candidate_score - current_score;
+
+    let temperature =
+      options.temperature * (1.0 - i as f64 / options.iterations as f64);
+    let accept = delta < 0.0
+      || (temperature > 0.0
+        && rng.random::<f64>() < f64::exp(-delta / temperature));
+
+    if accept {
+      current = candidate;
+      current_score = candidate_score;
+      if current_score < best.1 {
+        best = (current.clone(), current_score);
+      }
+    }
+  }
+
+  best
+}
@@ -1,3 +1,4 @@
+use crate::language::Language;
 use once_cell::sync::Lazy;
 
 #[macro_export]
@@ -54,3 +55,14 @@ pub static QUICK_BROWN_FOX: Lazy<String> =
 
 pub static QUICKBROWNFOX: Lazy<String> =
   lazy_read_to_string!("./test-texts/plaintexts/thequickbrownfox.txt");
+
+pub static A_TALE_OF_TWO_CITIES: Lazy<String> =
+  lazy_read_to_string!("./test-texts/plaintexts/a_tale_of_two_cities.txt");
+
+pub static ANNA_KARENINA: Lazy<String> =
+  lazy_read_to_string!("./test-texts/plaintexts/anna_karenina.txt");
+
+pub static HUMAN_RIGHTS_ARTICLE_1: Lazy<String> =
+  lazy_read_to_string!("./test-texts/plaintexts/human_rights_article_1.txt");
+
+pub static ENGLISH: Lazy<Language> = Lazy::new(Language::english);
@@ -0,0 +1,194 @@
+use crate::ngrams::RankedNgrams;
+use ahash::AHashMap;
+
+// Probability assigned to the rarest word when the model has no words.
+const EMPTY_MODEL_PROBABILITY: f64 = 1e-12;
+
+// Every letter of an unknown word costs this share of the log probability
+// of the rarest known word, and is this many times less likely on top of
+// it. An unknown word of two letters is then a hundred times less likely
+// than the rarest known word, so segmentation prefers dictionary words and
+// only leaves single letters unknown cheaply.
+const UNKNOWN_LETTER_SHARE: f64 = 0.5;
+const UNKNOWN_LETTER_PENALTY: f64 = 10.0;
+
+// How much the score grows as dictionary coverage falls, reaching this
+// fraction when no letter is in a known word. Short unknown words can have
+// a likelihood per letter close to that of real text, so without it a
+// segmentation of gibberish could outrank text made of dictionary words.
+const UNCOVERED_WEIGHT: f64 = 1.0;
+
+/// Word unigram and bigram log probabilities, built once from a language's
+/// word n-grams. Entries containing a space are treated as word bigrams.
+#[derive(Debug, Clone)]
+pub struct WordModel {
+  unigrams: AHashMap<String, f64>,
+  bigrams: AHashMap<(String, String), f64>,
+  max_word_len: usize,
+  unknown_letter: f64,
+}
+
+impl WordModel {
+  pub fn new(word_ngrams: &RankedNgrams) -> Self {
+    let mut unigrams = AHashMap::new();
+    let mut bigrams = AHashMap::new();
+
+    for (ngram, probability) in word_ngrams.iter() {
+      if *probability <= 0.0 {
+        continue;
+      }
+      let words: Vec<_> = ngram.split_whitespace().collect();
+      match words.as_slice() {
+        [word] => {
+          unigrams.insert(word.to_string(), probability.ln());
+        }
+        [a, b] => {
+          bigrams.insert((a.to_string(), b.to_string()), probability.ln());
+        }
+        _ => (),
+      }
+    }
+
+    let max_word_len = unigrams
+      .keys()
+      .map(|word| word.chars().count())
+      .max()
+      .unwrap_or(1);
+    let min_log_probability = unigrams
+      .values()
+      .copied()
+      .fold(EMPTY_MODEL_PROBABILITY.ln(), f64::min);
+
+    WordModel {
+      unigrams,
+      bigrams,
+      max_word_len,
+      unknown_letter: min_log_probability * UNKNOWN_LETTER_SHARE
+        - UNKNOWN_LETTER_PENALTY.ln(),
+    }
+  }
+
+  /// Whether the model knows no words, so that every text scores the same.
+  pub fn is_empty(&self) -> bool {
+    self.unigrams.is_empty()
+  }
+
+  fn word_log_probability(&self, word: &str) -> Option<f64> {
+    self.unigrams.get(word).copied()
+  }
+
+  fn unknown_log_probability(&self, len: usize) -> f64 {
+    self.unknown_letter * len as f64
+  }
+
+  /// Splits `text` into the most probable sequence of words. Runs of
+  /// letters between whitespace and punctuation are segmented separately,
+  /// so texts with spaces are mostly left as they are.
+  pub fn segment<'a>(&self, text: &'a str) -> Vec<&'a str> {
+    text
+      .split(|c: char| !c.is_alphabetic())
+      .filter(|token| !token.is_empty())
+      .flat_map(|token| self.segment_token(token))
+      .collect()
+  }
+
+  fn segment_token<'a>(&self, token: &'a str) -> Vec<&'a str> {
+    let offsets: Vec<usize> = token
+      .char_indices()
+      .map(|(i, _)| i)
+      .chain(std::iter::once(token.len()))
+      .collect();
+    let len = offsets.len() - 1;
+
+    // best[i] is the log probability of the best segmentation of the first
+    // i characters, and start[i] the character where its last word begins.
+    let mut best = vec![f64::NEG_INFINITY; len + 1];
+    let mut start = vec![0; len + 1];
+    best[0] = 0.0;
+
+    for end in 1..=len {
+      for begin in end.saturating_sub(self.max_word_len)..end {
+        let word = &token[offsets[begin]..offsets[end]];
+        let log_probability = self
+          .word_log_probability(word)
+          .unwrap_or_else(|| self.unknown_log_probability(end - begin));
+        let score = best[begin] + log_probability;
+        if score > best[end] {
+          best[end] = score;
+          start[end] = begin;
+        }
+      }
+    }
+
+    let mut words = Vec::new();
+    let mut end = len;
+    while end > 0 {
+      let begin = start[end];
+      words.push(&token[offsets[begin]..offsets[end]]);
+      end = begin;
+    }
+    words.reverse();
+
+    words
+  }
+
+  /// Fraction of the letters of `text` that fall inside dictionary words
+  /// after segmentation.
+  pub fn coverage(&self, text: &str) -> f64 {
+    self.known_fraction(&self.segment(text))
+  }
+
+  fn known_fraction(&self, words: &[&str]) -> f64 {
+    let (known, total) = words.iter().fold((0, 0), |(known, total), word| {
+      let len = word.chars().count();
+      if self.unigrams.contains_key(*word) {
+        (known + len, total + len)
+      } else {
+        (known, total + len)
+      }
+    });
+
+    if total == 0 {
+      return 0.0;
+    }
+
+    known as f64 / total as f64
+  }
+
+  /// Negative log likelihood per letter of the segmented text, using word
+  /// bigrams where the model has them, so that lower is better as with the
+  /// character n-gram confidences. It is scaled up as the coverage falls,
+  /// since a likely segmentation made of short unknown words is still
+  /// gibberish.
+  pub fn text_confidence(&self, text: &str) -> f64 {
+    let words = self.segment(text);
+    let letters: usize = words.iter().map(|word| word.chars().count()).sum();
+    if letters == 0 {
+      return f64::MAX;
+    }
+
+    let mut log_likelihood = 0.0;
+    let mut previous: Option<&str> = None;
+    for &word in &words {
+      let unigram = self.word_log_probability(word);
+      let bigram = previous.zip(unigram).and_then(|(previous, _)| {
+        let joint = self
+          .bigrams
+          .get(&(previous.to_string(), word.to_string()))?;
+        Some(joint - self.word_log_probability(previous)?)
+      });
+
+      log_likelihood += bigram
+        .or(unigram)
+        .unwrap_or_else(|| self.unknown_log_probability(word.chars().count()));
+      previous = Some(word);
+    }
+
+    let uncovered = 1.0 - self.known_fraction(&words);
+
+    -log_likelihood / letters as f64 * (1.0 + UNCOVERED_WEIGHT * uncovered)
+  }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,52 @@
+use super::*;
+use crate::tests::*;
+use once_cell::sync::Lazy;
+
+pub static MODEL: Lazy<WordModel> = Lazy::new(|| {
+  let word_ngrams = RankedNgrams::from_iter(
+    [
+      ("the", 60),
+      ("quick", 2),
+      ("brown", 3),
+      ("fox", 2),
+      ("jumps", 1),
+      ("over", 8),
+      ("lazy", 1),
+      ("dog", 4),
+      ("he", 20),
+      ("over the", 5),
+    ]
+    .into_iter()
+    .map(|(ngram, count)| (ngram.to_string(), count as u64)),
+  );
+
+  WordModel::new(&word_ngrams)
+});
+
+#[test]
+fn segment() {
+  assert_eq!(
+    MODEL.segment(&QUICKBROWNFOX),
+    QUICK_BROWN_FOX.split_whitespace().collect::<Vec<_>>()
+  );
+}
+
+#[test]
+fn coverage() {
+  assert_eq!(MODEL.coverage(&QUICKBROWNFOX), 1.0);
+  assert_eq!(MODEL.coverage("the xqzv"), 3.0 / 7.0);
+}
+
+#[test]
+fn text_confidence() {
+  let plaintext = MODEL.text_confidence(&QUICK_BROWN_FOX);
+
+  assert_eq!(plaintext, MODEL.text_confidence(&QUICKBROWNFOX));
+  assert!(plaintext < MODEL.text_confidence("uif rvjdl cspxo gpy"));
+}
+
+#[test]
+fn is_empty() {
+  assert!(!MODEL.is_empty());
+  assert!(WordModel::new(&RankedNgrams::default()).is_empty());
+}